@@ -0,0 +1,44 @@
+use std::io;
+use std::process::exit;
+
+use advent_of_code_2022::day05::crane::crane_by_name;
+use advent_of_code_2022::day05::dock::parse;
+use advent_of_code_2022::day05::replay::{export, frames, step_through, Replay};
+
+const USAGE: &str = "Usage: day05_replay <input file> [crane (9000, 9001, limited-K)] [--export]";
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let export_frames = args.iter().any(|a| a == "--export");
+    let args = args.iter().filter(|a| *a != "--export").collect::<Vec<_>>();
+
+    let Some(path) = args.first() else {
+        eprintln!("{USAGE}");
+        exit(1);
+    };
+    let crane_name = args.get(1).map(|s| s.as_str()).unwrap_or("9000");
+
+    let input = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("Unable to read {path}: {e}");
+        exit(1);
+    });
+    let (dock, moves) = parse(&input).unwrap_or_else(|e| {
+        eprintln!("Unable to parse {path}: {e}");
+        exit(1);
+    });
+    let mut crane = crane_by_name(crane_name).unwrap_or_else(|| {
+        eprintln!("Unknown crane '{crane_name}'\n{USAGE}");
+        exit(1);
+    });
+
+    if export_frames {
+        let frames = frames(dock, &moves, crane.as_mut()).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            exit(1);
+        });
+        export(&frames, &mut io::stdout()).unwrap();
+    } else {
+        let replay = Replay::new(dock, &moves, crane.as_mut());
+        step_through(replay, &mut io::stdin().lock(), &mut io::stdout()).unwrap();
+    }
+}
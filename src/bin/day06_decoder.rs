@@ -0,0 +1,31 @@
+use std::fs::File;
+use std::io;
+use std::process::exit;
+
+use advent_of_code_2022::day06::decoder::decode;
+
+const USAGE: &str = "Usage: day06_decoder [input file, reads stdin if missing or -]";
+
+fn main() {
+    let path = std::env::args().nth(1);
+
+    let result = match path.as_deref() {
+        None | Some("-") => decode(io::stdin().lock(), |event| println!("{event}")),
+        Some("-h" | "--help") => {
+            println!("{USAGE}");
+            return;
+        },
+        Some(path) => {
+            let file = File::open(path).unwrap_or_else(|e| {
+                eprintln!("Unable to open {path}: {e}");
+                exit(1);
+            });
+            decode(file, |event| println!("{event}"))
+        },
+    };
+
+    if let Err(e) = result {
+        eprintln!("Unable to read signal: {e}");
+        exit(1);
+    }
+}
@@ -0,0 +1,143 @@
+use std::path::Path;
+use std::process::exit;
+
+use advent_of_code_2022::day07::filesystem::Filesystem;
+use advent_of_code_2022::day07::json::{from_json, to_json};
+use advent_of_code_2022::day07::lines::ParsedLine;
+use advent_of_code_2022::day07::report::{du, find, format_sizes, largest, parse_size, tree, FindQuery, Kind, SortBy};
+use advent_of_code_2022::day07::transcript::transcript;
+
+const USAGE: &str = "Usage: day07 <transcript file> <command> [options]
+       day07 --from-dir <directory> [command] [options]
+       day07 --from-json <json file> <command> [options]
+--from-dir uses a transcript of a real directory, and prints it if there's no command
+Commands:
+  json
+  du [--depth N] [--sort path|size] [-h]
+  tree [-h]
+  find [--min SIZE] [--max SIZE] [--name GLOB] [--type f|d] [-h]
+  largest N [--type f|d] [-h]
+-h shows human-readable sizes, sizes can have a K, M, G or T suffix";
+
+fn fail(message: impl std::fmt::Display) -> ! {
+    eprintln!("{message}");
+    exit(1);
+}
+
+/// Options given as `--name value`, and whether `-h` was given
+struct Options {
+    values: Vec<(String, String)>,
+    human: bool,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Self {
+        let mut values = vec![];
+        let mut human = false;
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            if arg == "-h" {
+                human = true;
+            } else if let Some(name) = arg.strip_prefix("--") {
+                let value = args.next().unwrap_or_else(|| fail(format!("Missing value for {arg}\n{USAGE}")));
+                values.push((name.to_string(), value.clone()));
+            } else {
+                fail(format!("Unexpected argument '{arg}'\n{USAGE}"));
+            }
+        }
+
+        Self { values, human }
+    }
+
+    fn get<T>(&self, name: &str, parse: impl Fn(&str) -> Result<T, String>) -> Option<T> {
+        self.values
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| parse(value).unwrap_or_else(|e| fail(e)))
+    }
+}
+
+fn load(input: &str) -> Filesystem {
+    let lines = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| ParsedLine::try_from(line).unwrap_or_else(|e| fail(format!("line {}: {e} '{line}'", i + 1))))
+        .collect();
+
+    let mut fs = Filesystem::new();
+    let conflicts = fs.exec_lines(lines).unwrap_or_else(|e| fail(e));
+    for conflict in conflicts {
+        eprintln!("warning: {conflict}");
+    }
+
+    fs
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    let source = args.first().cloned();
+    if matches!(source.as_deref(), Some("--from-dir" | "--from-json")) {
+        args.remove(0);
+    }
+    let Some(path) = args.first() else {
+        fail(USAGE);
+    };
+
+    let fs = match source.as_deref() {
+        Some("--from-dir") => {
+            let input = transcript(Path::new(path)).unwrap_or_else(|e| fail(format!("Unable to read {path}: {e}")));
+            if args.len() == 1 {
+                print!("{input}");
+                return;
+            }
+            load(&input)
+        },
+        Some("--from-json") => {
+            let input = std::fs::read_to_string(path).unwrap_or_else(|e| fail(format!("Unable to read {path}: {e}")));
+            from_json(&input).unwrap_or_else(|e| fail(format!("Unable to load {path}: {e}")))
+        },
+        _ => load(&std::fs::read_to_string(path).unwrap_or_else(|e| fail(format!("Unable to read {path}: {e}")))),
+    };
+
+    let Some(command) = args.get(1).map(|c| c.as_str()) else {
+        fail(USAGE);
+    };
+
+    let output = match command {
+        "du" => {
+            let options = Options::parse(&args[2..]);
+            let max_depth = options.get("depth", |s| s.parse::<usize>().map_err(|e| format!("Invalid depth '{s}': {e}")));
+            let sort = options.get("sort", str::parse::<SortBy>).unwrap_or(SortBy::Path);
+            format_sizes(&du(&fs, max_depth, sort), options.human)
+        },
+        "json" => to_json(&fs),
+        "tree" => tree(&fs, Options::parse(&args[2..]).human),
+        "find" => {
+            let options = Options::parse(&args[2..]);
+            let query = FindQuery {
+                min_size: options.get("min", parse_size),
+                max_size: options.get("max", parse_size),
+                name: options.get("name", |s| Ok(s.to_string())),
+                kind: options.get("type", str::parse::<Kind>),
+            };
+            let sizes = find(&fs, &query).into_iter().map(|(path, node)| (path, node.size())).collect::<Vec<_>>();
+            format_sizes(&sizes, options.human)
+        },
+        "largest" => {
+            let n = args
+                .get(2)
+                .and_then(|s| s.parse::<usize>().ok())
+                .unwrap_or_else(|| fail(format!("largest needs a count\n{USAGE}")));
+            let options = Options::parse(&args[3..]);
+            format_sizes(&largest(&fs, n, options.get("type", str::parse::<Kind>)), options.human)
+        },
+        _ => fail(format!("Unknown command '{command}'\n{USAGE}")),
+    };
+
+    if !output.is_empty() {
+        println!("{output}");
+    }
+}
@@ -0,0 +1,170 @@
+use std::fmt::Display;
+use itertools::Itertools;
+
+use super::{round_score, Outcome, Shape};
+
+const COLUMNS: [&str; 3] = ["X", "Y", "Z"];
+
+/// A single line of the strategy guide. `column` is the index of the second column (X=0, Y=1, Z=2)
+/// so it can be interpreted any way we like
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuideLine {
+    pub opponent: Shape,
+    pub column: usize,
+}
+
+pub fn parse_guide(input: &str) -> Vec<GuideLine> {
+    input
+        .lines()
+        .map(|line| {
+            let (a, b) = line.split_once(' ').unwrap();
+            let opponent = Shape::from_opponent_column(a)
+                .unwrap_or_else(|| panic!("Unknown moves {} {}", a, b));
+            let column = COLUMNS
+                .iter()
+                .position(|c| *c == b)
+                .unwrap_or_else(|| panic!("Unknown moves {} {}", a, b));
+
+            GuideLine { opponent, column }
+        })
+        .collect()
+}
+
+/// What the elf might have meant by X/Y/Z, either the shape to play or the outcome to aim for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mapping {
+    Shapes([Shape; 3]),
+    Outcomes([Outcome; 3]),
+}
+
+impl Mapping {
+    /// Every permutation of shapes and every permutation of outcomes
+    pub fn all() -> Vec<Mapping> {
+        let shapes = Shape::ALL
+            .into_iter()
+            .permutations(3)
+            .map(|p| Mapping::Shapes(p.try_into().unwrap()));
+        let outcomes = Outcome::ALL
+            .into_iter()
+            .permutations(3)
+            .map(|p| Mapping::Outcomes(p.try_into().unwrap()));
+
+        shapes.chain(outcomes).collect()
+    }
+
+    pub fn response(&self, line: &GuideLine) -> Shape {
+        match self {
+            Mapping::Shapes(shapes) => shapes[line.column],
+            Mapping::Outcomes(outcomes) => outcomes[line.column].response_to(line.opponent),
+        }
+    }
+
+    pub fn score(&self, guide: &[GuideLine]) -> i32 {
+        guide
+            .iter()
+            .map(|line| round_score(self.response(line), line.opponent))
+            .sum()
+    }
+}
+
+impl Display for Mapping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let meanings = match self {
+            Mapping::Shapes(shapes) => shapes.iter().map(|s| format!("{s:?}")).collect::<Vec<_>>(),
+            Mapping::Outcomes(outcomes) => outcomes.iter().map(|o| format!("{o:?}")).collect::<Vec<_>>(),
+        };
+
+        let s = COLUMNS
+            .iter()
+            .zip(meanings)
+            .map(|(column, meaning)| format!("{column}={meaning}"))
+            .join(", ");
+        write!(f, "{s}")
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MappingScore {
+    pub mapping: Mapping,
+    pub score: i32,
+}
+
+/// Score of the strategy guide under every possible mapping
+#[derive(Debug)]
+pub struct Analysis {
+    pub scores: Vec<MappingScore>,
+}
+
+impl Analysis {
+    pub fn new(guide: &[GuideLine]) -> Self {
+        let scores = Mapping::all()
+            .into_iter()
+            .map(|mapping| MappingScore { mapping, score: mapping.score(guide) })
+            .collect();
+
+        Self { scores }
+    }
+
+    pub fn best(&self) -> &MappingScore {
+        self.scores.iter().max_by_key(|s| s.score).unwrap()
+    }
+
+    pub fn worst(&self) -> &MappingScore {
+        self.scores.iter().min_by_key(|s| s.score).unwrap()
+    }
+}
+
+impl Display for Analysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for MappingScore { mapping, score } in &self.scores {
+            writeln!(f, "{mapping}: {score}")?;
+        }
+        writeln!(f, "best: {} ({})", self.best().mapping, self.best().score)?;
+        write!(f, "worst: {} ({})", self.worst().mapping, self.worst().score)
+    }
+}
+
+#[cfg(test)]
+mod day2_analysis_tests {
+    use super::*;
+    use crate::day02::{part1, part2};
+
+    const SAMPLE_INPUT: &str = concat!(
+        "A Y\n",
+        "B X\n",
+        "C Z\n",
+    );
+
+    #[test]
+    fn default_mappings_match_parts() {
+        let guide = parse_guide(SAMPLE_INPUT);
+
+        let shapes = Mapping::Shapes([Shape::Rock, Shape::Paper, Shape::Scissors]);
+        let outcomes = Mapping::Outcomes([Outcome::Loss, Outcome::Draw, Outcome::Win]);
+
+        assert_eq!(shapes.score(&guide), part1(SAMPLE_INPUT));
+        assert_eq!(outcomes.score(&guide), part2(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn analysis_covers_every_mapping() {
+        let guide = parse_guide(SAMPLE_INPUT);
+        let analysis = Analysis::new(&guide);
+
+        assert_eq!(analysis.scores.len(), 12);
+        assert!(analysis.scores.iter().all(|s| s.score <= analysis.best().score));
+        assert!(analysis.scores.iter().all(|s| s.score >= analysis.worst().score));
+    }
+
+    #[test]
+    fn best_and_worst_sample_mappings() {
+        let guide = parse_guide(SAMPLE_INPUT);
+        let analysis = Analysis::new(&guide);
+
+        // Winning every round: 3 * 6 + Paper + Scissors + Rock
+        assert_eq!(analysis.best().score, 24);
+        // Losing every round: Scissors + Rock + Paper
+        assert_eq!(analysis.worst().score, 6);
+        assert_eq!(analysis.best().mapping, Mapping::Shapes([Shape::Scissors, Shape::Paper, Shape::Rock]));
+    }
+}
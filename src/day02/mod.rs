@@ -0,0 +1,154 @@
+pub mod analysis;
+pub mod tournament;
+
+#[allow(clippy::get_first)]
+
+#[aoc(day2, part1)]
+pub fn part1(input: &str) -> i32 {
+    input.lines().map(|l| part1_get_score(l.split_once(' ').unwrap())).sum()
+}
+
+#[aoc(day2, part2)]
+pub fn part2(input: &str) -> i32 {
+    input.lines().map(|l| part2_get_score(l.split_once(' ').unwrap())).sum()
+}
+
+const LOSS_SCORE: i32 = 0;
+const DRAW_SCORE: i32 = 3;
+const WIN_SCORE: i32 = 6;
+const ROCK_SCORE: i32 = 1;
+const PAPER_SCORE: i32 = 2;
+const SCISSORS_SCORE: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Shape {
+    pub const ALL: [Shape; 3] = [Shape::Rock, Shape::Paper, Shape::Scissors];
+
+    pub fn score(&self) -> i32 {
+        match self {
+            Shape::Rock => ROCK_SCORE,
+            Shape::Paper => PAPER_SCORE,
+            Shape::Scissors => SCISSORS_SCORE,
+        }
+    }
+
+    /// The shape this one defeats
+    pub fn beats(&self) -> Shape {
+        match self {
+            Shape::Rock => Shape::Scissors,
+            Shape::Paper => Shape::Rock,
+            Shape::Scissors => Shape::Paper,
+        }
+    }
+
+    /// The shape that defeats this one
+    pub fn beaten_by(&self) -> Shape {
+        match self {
+            Shape::Rock => Shape::Paper,
+            Shape::Paper => Shape::Scissors,
+            Shape::Scissors => Shape::Rock,
+        }
+    }
+
+    /// Parses the opponent's column of the strategy guide
+    pub fn from_opponent_column(s: &str) -> Option<Shape> {
+        match s {
+            "A" => Some(Shape::Rock),
+            "B" => Some(Shape::Paper),
+            "C" => Some(Shape::Scissors),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+
+    pub fn score(&self) -> i32 {
+        match self {
+            Outcome::Loss => LOSS_SCORE,
+            Outcome::Draw => DRAW_SCORE,
+            Outcome::Win => WIN_SCORE,
+        }
+    }
+
+    /// Outcome of a round from the point of view of whoever played `me`
+    pub fn of(me: Shape, opponent: Shape) -> Outcome {
+        if me == opponent {
+            Outcome::Draw
+        } else if me.beats() == opponent {
+            Outcome::Win
+        } else {
+            Outcome::Loss
+        }
+    }
+
+    /// Shape to play against `opponent` to get this outcome
+    pub fn response_to(&self, opponent: Shape) -> Shape {
+        match self {
+            Outcome::Loss => opponent.beats(),
+            Outcome::Draw => opponent,
+            Outcome::Win => opponent.beaten_by(),
+        }
+    }
+}
+
+pub fn round_score(me: Shape, opponent: Shape) -> i32 {
+    Outcome::of(me, opponent).score() + me.score()
+}
+
+fn part1_get_score(moves: (&str, &str)) -> i32 {
+    match moves {
+        // Losses
+        ("B", "X") => LOSS_SCORE + ROCK_SCORE,
+        ("C", "Y") => LOSS_SCORE + PAPER_SCORE,
+        ("A", "Z") => LOSS_SCORE + SCISSORS_SCORE,
+
+        // Draws
+        ("A", "X") => DRAW_SCORE + ROCK_SCORE,
+        ("B", "Y") => DRAW_SCORE + PAPER_SCORE,
+        ("C", "Z") => DRAW_SCORE + SCISSORS_SCORE,
+
+        // Wins
+        ("C", "X") => WIN_SCORE + ROCK_SCORE,
+        ("A", "Y") => WIN_SCORE + PAPER_SCORE,
+        ("B", "Z") => WIN_SCORE + SCISSORS_SCORE,
+
+        _ => panic!("Unknown moves {} {}", moves.0, moves.1)
+    }
+}
+
+fn part2_get_score(moves: (&str, &str)) -> i32 {
+    match moves {
+        // Losses
+        ("A", "X") => LOSS_SCORE + SCISSORS_SCORE,
+        ("B", "X") => LOSS_SCORE + ROCK_SCORE,
+        ("C", "X") => LOSS_SCORE + PAPER_SCORE,
+
+        // Draws
+        ("A", "Y") => DRAW_SCORE + ROCK_SCORE,
+        ("B", "Y") => DRAW_SCORE + PAPER_SCORE,
+        ("C", "Y") => DRAW_SCORE + SCISSORS_SCORE,
+        
+        // Wins
+        ("A", "Z") => WIN_SCORE + PAPER_SCORE,
+        ("B", "Z") => WIN_SCORE + SCISSORS_SCORE,
+        ("C", "Z") => WIN_SCORE + ROCK_SCORE,
+
+        _ => panic!("Unknown moves {} {}", moves.0, moves.1)
+    }
+}
+
@@ -0,0 +1,246 @@
+use std::fmt::Display;
+
+use super::analysis::{GuideLine, Mapping};
+use super::{round_score, Outcome, Shape};
+
+/// A played round from the point of view of one side
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    pub own: Shape,
+    pub other: Shape,
+}
+
+pub trait Strategy {
+    /// Picks the next shape given every round played so far, from this side's point of view
+    fn choose(&mut self, history: &[Round]) -> Shape;
+}
+
+/// Always plays the same shape
+pub struct Fixed(pub Shape);
+
+impl Strategy for Fixed {
+    fn choose(&mut self, _history: &[Round]) -> Shape {
+        self.0
+    }
+}
+
+/// Plays uniformly random shapes from a seeded generator, so runs are reproducible
+pub struct UniformRandom {
+    rng: SplitMix64,
+}
+
+impl UniformRandom {
+    pub fn new(seed: u64) -> Self {
+        Self { rng: SplitMix64(seed) }
+    }
+}
+
+impl Strategy for UniformRandom {
+    fn choose(&mut self, _history: &[Round]) -> Shape {
+        Shape::ALL[(self.rng.next() % 3) as usize]
+    }
+}
+
+/// Assumes the other side will repeat its most frequent shape so far, and plays what beats it
+pub struct FrequencyAdaptive;
+
+impl Strategy for FrequencyAdaptive {
+    fn choose(&mut self, history: &[Round]) -> Shape {
+        let mut counts = [0; 3];
+        for round in history {
+            counts[Shape::ALL.iter().position(|s| *s == round.other).unwrap()] += 1;
+        }
+
+        // Ties go to the earliest shape in `Shape::ALL`
+        let (most_common, _) = Shape::ALL
+            .iter()
+            .zip(counts)
+            .rev()
+            .max_by_key(|(_, count)| *count)
+            .unwrap();
+
+        most_common.beaten_by()
+    }
+}
+
+/// Plays a fixed sequence of shapes, starting over when it runs out
+pub struct Replay {
+    shapes: Vec<Shape>,
+    next: usize,
+}
+
+impl Replay {
+    pub fn new(shapes: Vec<Shape>) -> Self {
+        assert!(!shapes.is_empty(), "Cannot replay an empty sequence of shapes");
+        Self { shapes, next: 0 }
+    }
+
+    /// Replays the opponent's column of the strategy guide
+    pub fn guide_opponent(guide: &[GuideLine]) -> Self {
+        Self::new(guide.iter().map(|line| line.opponent).collect())
+    }
+
+    /// Replays our column of the strategy guide, interpreted with `mapping`
+    pub fn guide_player(guide: &[GuideLine], mapping: &Mapping) -> Self {
+        Self::new(guide.iter().map(|line| mapping.response(line)).collect())
+    }
+}
+
+impl Strategy for Replay {
+    fn choose(&mut self, _history: &[Round]) -> Shape {
+        let shape = self.shapes[self.next];
+        self.next = (self.next + 1) % self.shapes.len();
+        shape
+    }
+}
+
+/// Results of a tournament from the player's point of view
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Report {
+    pub rounds: usize,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    /// Expected score of a single round
+    pub mean_score: f64,
+    /// Population variance of the score of a single round
+    pub variance: f64,
+}
+
+impl Report {
+    pub fn win_rate(&self) -> f64 {
+        self.rate(self.wins)
+    }
+
+    pub fn draw_rate(&self) -> f64 {
+        self.rate(self.draws)
+    }
+
+    pub fn loss_rate(&self) -> f64 {
+        self.rate(self.losses)
+    }
+
+    /// Fraction of rounds `count` is, 0 if no rounds were played
+    fn rate(&self, count: usize) -> f64 {
+        if self.rounds > 0 { count as f64 / self.rounds as f64 } else { 0.0 }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} rounds: mean score {:.3}, variance {:.3}, win {:.1}%, draw {:.1}%, loss {:.1}%",
+            self.rounds,
+            self.mean_score,
+            self.variance,
+            self.win_rate() * 100.0,
+            self.draw_rate() * 100.0,
+            self.loss_rate() * 100.0,
+        )
+    }
+}
+
+pub fn simulate(player: &mut dyn Strategy, opponent: &mut dyn Strategy, rounds: usize) -> Report {
+    let mut player_history: Vec<Round> = Vec::with_capacity(rounds);
+    let mut opponent_history: Vec<Round> = Vec::with_capacity(rounds);
+
+    let (mut wins, mut draws, mut losses) = (0, 0, 0);
+
+    // Welford's algorithm so we don't need to keep every score around
+    let mut mean = 0.0;
+    let mut m2 = 0.0;
+
+    for n in 1..=rounds {
+        let own = player.choose(&player_history);
+        let other = opponent.choose(&opponent_history);
+
+        match Outcome::of(own, other) {
+            Outcome::Win => wins += 1,
+            Outcome::Draw => draws += 1,
+            Outcome::Loss => losses += 1,
+        }
+
+        let score = round_score(own, other) as f64;
+        let delta = score - mean;
+        mean += delta / n as f64;
+        m2 += delta * (score - mean);
+
+        player_history.push(Round { own, other });
+        opponent_history.push(Round { own: other, other: own });
+    }
+
+    let variance = if rounds > 0 { m2 / rounds as f64 } else { 0.0 };
+
+    Report { rounds, wins, draws, losses, mean_score: mean, variance }
+}
+
+/// Small PRNG so seeded simulations don't need an extra dependency
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod day2_tournament_tests {
+    use super::*;
+    use crate::day02::analysis::parse_guide;
+    use crate::day02::part1;
+
+    #[test]
+    fn fixed_against_fixed() {
+        let report = simulate(&mut Fixed(Shape::Paper), &mut Fixed(Shape::Rock), 10);
+
+        assert_eq!(report.wins, 10);
+        assert_eq!(report.mean_score, 8.0);
+        assert_eq!(report.variance, 0.0);
+    }
+
+    #[test]
+    fn no_rounds() {
+        let report = simulate(&mut Fixed(Shape::Paper), &mut Fixed(Shape::Rock), 0);
+        assert_eq!((report.win_rate(), report.draw_rate(), report.loss_rate()), (0.0, 0.0, 0.0));
+        assert_eq!(report.to_string(), "0 rounds: mean score 0.000, variance 0.000, win 0.0%, draw 0.0%, loss 0.0%");
+    }
+
+    #[test]
+    fn random_is_reproducible_and_roughly_uniform() {
+        let report1 = simulate(&mut UniformRandom::new(1), &mut UniformRandom::new(2), 30_000);
+        let report2 = simulate(&mut UniformRandom::new(1), &mut UniformRandom::new(2), 30_000);
+        assert_eq!(report1, report2);
+
+        for rate in [report1.win_rate(), report1.draw_rate(), report1.loss_rate()] {
+            assert!((rate - 1.0 / 3.0).abs() < 0.02, "rate {rate} isn't close to 1/3");
+        }
+    }
+
+    #[test]
+    fn frequency_adaptive_exploits_fixed() {
+        let report = simulate(&mut FrequencyAdaptive, &mut Fixed(Shape::Scissors), 100);
+
+        // The first round is a guess, every round after that is a win
+        assert_eq!(report.wins, 99);
+    }
+
+    #[test]
+    fn replaying_guide_matches_part1() {
+        let input = "A Y\nB X\nC Z\n";
+        let guide = parse_guide(input);
+        let mapping = Mapping::Shapes(Shape::ALL);
+
+        let report = simulate(
+            &mut Replay::guide_player(&guide, &mapping),
+            &mut Replay::guide_opponent(&guide),
+            guide.len(),
+        );
+
+        assert_eq!(report.mean_score * guide.len() as f64, part1(input) as f64);
+    }
+}
@@ -0,0 +1,122 @@
+use itertools::Itertools;
+
+pub mod rucksack;
+use rucksack::Rucksack;
+
+pub mod validation;
+
+#[aoc(day3, part1)]
+pub fn part1(input: &str) -> u32 {
+    shared_items(input, 2)
+        .unwrap_or_else(|e| panic!("Unable to find badges: {e}"))
+        .iter()
+        .flat_map(Rucksack::priorities)
+        .sum()
+}
+
+#[aoc(day3, part2)]
+pub fn part2(input: &str) -> u32 {
+    badges(input, 3)
+        .unwrap_or_else(|e| panic!("Unable to find badges: {e}"))
+        .iter()
+        .flat_map(Rucksack::priorities)
+        .sum()
+}
+
+/// Items common to every compartment of each rucksack, one entry per line
+pub fn shared_items(input: &str, compartments: usize) -> Result<Vec<Rucksack>, String> {
+    input
+        .lines()
+        .map(|line| compartment_items(line, compartments))
+        .collect()
+}
+
+/// Items common to all `compartments` equally sized compartments of a single rucksack
+pub fn compartment_items(line: &str, compartments: usize) -> Result<Rucksack, String> {
+    if compartments == 0 || !line.len().is_multiple_of(compartments) {
+        return Err(format!("Rucksack '{line}' can't be split into {compartments} equal compartments"));
+    }
+    if line.is_empty() {
+        return Ok(Rucksack::default());
+    }
+
+    line.as_bytes()
+        .chunks(line.len() / compartments)
+        .map(Rucksack::from_bytes)
+        .fold_ok(Rucksack::ALL, |acc, r| acc & r)
+}
+
+/// Items common to every rucksack in each group of `group_size` elves, one entry per group
+pub fn badges(input: &str, group_size: usize) -> Result<Vec<Rucksack>, String> {
+    if group_size == 0 {
+        return Err("Group size must be at least 1".to_string());
+    }
+
+    let lines = input.lines().collect::<Vec<_>>();
+
+    lines
+        .chunks(group_size)
+        .map(|group| {
+            if group.len() != group_size {
+                return Err(format!("Partial group of {} rucksacks at end of input, expected {group_size}", group.len()));
+            }
+
+            group
+                .iter()
+                .map(|line| Rucksack::from_bytes(line.as_bytes()))
+                .fold_ok(Rucksack::ALL, |acc, r| acc & r)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod day3_tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = concat!(
+        "vJrwpWtwJgWrhcsFMMfFFhFp\n",
+        "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n",
+        "PmmdzqPrVvPwwTWBwg\n",
+        "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n",
+        "ttgJtRGJQctTZtZT\n",
+        "CrZsJsPPZsGzwwsLwLmpwMDw\n",
+    );
+
+    #[test]
+    fn part1_sample_input() {
+        let answer = part1(SAMPLE_INPUT);
+        assert_eq!(answer, 157);
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let answer = part2(SAMPLE_INPUT);
+        assert_eq!(answer, 70);
+    }
+
+    #[test]
+    fn badges_for_groups_of_4() {
+        let input = "abcX\nXbde\nfXbg\nhibX\n";
+        let badges = badges(input, 4).unwrap();
+
+        assert_eq!(badges.len(), 1);
+        assert_eq!(badges[0].priorities().collect::<Vec<_>>(), vec![2, 50]);
+    }
+
+    #[test]
+    fn partial_group_is_an_error() {
+        assert!(badges(SAMPLE_INPUT, 4).is_err());
+        assert!(badges(SAMPLE_INPUT, 3).is_ok());
+    }
+
+    #[test]
+    fn multiple_shared_items_across_compartments() {
+        let items = compartment_items("abcacbbca", 3).unwrap();
+        assert_eq!(items.priorities().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        let items = compartment_items("abXYabYZ", 2).unwrap();
+        assert_eq!(items.priorities().collect::<Vec<_>>(), vec![1, 2, 51]);
+
+        assert!(compartment_items("abc", 2).is_err());
+    }
+}
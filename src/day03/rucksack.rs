@@ -0,0 +1,120 @@
+use std::ops::{BitAnd, BitOr};
+
+/// Set of items, stored as a mask where bit `n` is set if the item with priority `n` is present.
+/// Priorities go from 1 to 52 so everything fits in a `u64`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Rucksack(u64);
+
+impl Rucksack {
+    /// Every valid item, the identity for intersection
+    pub const ALL: Rucksack = Rucksack(((1 << 52) - 1) << 1);
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let mut mask = 0;
+        for b in bytes {
+            let priority = get_priority(*b).ok_or_else(|| format!("Non-alphabetical character '{}'", *b as char))?;
+            mask |= 1 << priority;
+        }
+
+        Ok(Self(mask))
+    }
+
+    pub fn contains(&self, priority: u32) -> bool {
+        priority < 64 && self.0 & (1 << priority) != 0
+    }
+
+    pub fn intersection(&self, other: &Rucksack) -> Rucksack {
+        Rucksack(self.0 & other.0)
+    }
+
+    pub fn union(&self, other: &Rucksack) -> Rucksack {
+        Rucksack(self.0 | other.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Number of distinct items
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Priorities of the items in the rucksack, lowest first
+    pub fn priorities(&self) -> Priorities {
+        Priorities(self.0)
+    }
+}
+
+impl BitAnd for Rucksack {
+    type Output = Rucksack;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(&rhs)
+    }
+}
+
+impl BitOr for Rucksack {
+    type Output = Rucksack;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(&rhs)
+    }
+}
+
+pub struct Priorities(u64);
+
+impl Iterator for Priorities {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let priority = self.0.trailing_zeros();
+        // Clear lowest set bit
+        self.0 &= self.0 - 1;
+        Some(priority)
+    }
+}
+
+pub fn get_priority(b: u8) -> Option<u32> {
+    match b {
+        b'a'..=b'z' => Some((b - b'a') as u32 + 1),
+        b'A'..=b'Z' => Some((b - b'A') as u32 + 27),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod day3_rucksack_tests {
+    use super::*;
+
+    #[test]
+    fn priorities() {
+        assert_eq!(get_priority(b'a'), Some(1));
+        assert_eq!(get_priority(b'z'), Some(26));
+        assert_eq!(get_priority(b'A'), Some(27));
+        assert_eq!(get_priority(b'Z'), Some(52));
+        assert_eq!(get_priority(b'1'), None);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = Rucksack::from_bytes(b"abcA").unwrap();
+        let b = Rucksack::from_bytes(b"cAZ").unwrap();
+
+        assert_eq!((a & b).priorities().collect::<Vec<_>>(), vec![3, 27]);
+        assert_eq!((a | b).priorities().collect::<Vec<_>>(), vec![1, 2, 3, 27, 52]);
+        assert_eq!(a.len(), 4);
+        assert!(a.contains(1));
+        assert!(!a.contains(52));
+        assert!(Rucksack::from_bytes(b"ab").unwrap().intersection(&b).is_empty());
+    }
+
+    #[test]
+    fn invalid_character() {
+        assert!(Rucksack::from_bytes(b"ab-c").is_err());
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+
+use itertools::Itertools;
+
+use super::rucksack::{get_priority, Rucksack};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reason {
+    /// Line can't be split into equally sized compartments
+    UnevenLength { len: usize, compartments: usize },
+    InvalidCharacter(char),
+    NoCommonItem,
+    MultipleCommonItems(Vec<u32>),
+    /// Not enough rucksacks left at the end of the input to fill the last group
+    PartialGroup { len: usize, group_size: usize },
+    NoBadge,
+    MultipleBadges(Vec<u32>),
+}
+
+impl Display for Reason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reason::UnevenLength { len, compartments } => write!(f, "length {len} can't be split into {compartments} equal compartments"),
+            Reason::InvalidCharacter(ch) => write!(f, "invalid character '{ch}'"),
+            Reason::NoCommonItem => write!(f, "no item common to every compartment"),
+            Reason::MultipleCommonItems(priorities) => write!(f, "more than one common item (priorities {priorities:?})"),
+            Reason::PartialGroup { len, group_size } => write!(f, "group of {len} rucksacks at end of input, expected {group_size}"),
+            Reason::NoBadge => write!(f, "no item common to every rucksack in the group"),
+            Reason::MultipleBadges(priorities) => write!(f, "more than one badge (priorities {priorities:?})"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// 1-based, to match what an editor shows
+    pub line_number: usize,
+    pub line: String,
+    pub reason: Reason,
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {} '{}': {}", self.line_number, self.line, self.reason)
+    }
+}
+
+/// What to do with lines that have problems when solving
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnInvalid {
+    Skip,
+    Fail,
+}
+
+/// Every problem with a single rucksack. An uneven or invalid line isn't checked for common items
+pub fn validate_line(line: &str, compartments: usize) -> Vec<Reason> {
+    let mut reasons = vec![];
+
+    if compartments == 0 || !line.len().is_multiple_of(compartments) {
+        reasons.push(Reason::UnevenLength { len: line.len(), compartments });
+    }
+
+    reasons.extend(invalid_characters(line));
+
+    if !reasons.is_empty() {
+        return reasons;
+    }
+
+    let common = super::compartment_items(line, compartments).unwrap();
+    match common.len() {
+        0 => reasons.push(Reason::NoCommonItem),
+        1 => {},
+        _ => reasons.push(Reason::MultipleCommonItems(common.priorities().collect())),
+    }
+
+    reasons
+}
+
+fn invalid_characters(line: &str) -> impl Iterator<Item = Reason> + '_ {
+    line.chars()
+        .filter(|ch| !ch.is_ascii() || get_priority(*ch as u8).is_none())
+        .map(Reason::InvalidCharacter)
+}
+
+/// Items in every rucksack of a group whose lines are all valid
+fn group_items(group: &[&str]) -> Rucksack {
+    group
+        .iter()
+        .map(|line| Rucksack::from_bytes(line.as_bytes()))
+        .fold_ok(Rucksack::ALL, |acc, r| acc & r)
+        .unwrap()
+}
+
+pub fn validate(input: &str, compartments: usize) -> Vec<Problem> {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(i, line)| {
+            validate_line(line, compartments)
+                .into_iter()
+                .map(move |reason| Problem { line_number: i + 1, line: line.to_string(), reason })
+        })
+        .collect()
+}
+
+/// Every problem with each group of `group_size` rucksacks. Invalid characters are reported on their own
+/// line, anything wrong with the group as a whole on its first line
+pub fn validate_groups(input: &str, group_size: usize) -> Vec<Problem> {
+    assert!(group_size > 0, "Groups need at least 1 rucksack");
+
+    let lines = input.lines().collect::<Vec<_>>();
+    let mut problems = vec![];
+
+    for (group_index, group) in lines.chunks(group_size).enumerate() {
+        let first_line_number = group_index * group_size + 1;
+        let problems_before = problems.len();
+
+        for (i, line) in group.iter().enumerate() {
+            problems.extend(
+                invalid_characters(line)
+                    .map(|reason| Problem { line_number: first_line_number + i, line: line.to_string(), reason })
+            );
+        }
+
+        let reason = if group.len() != group_size {
+            Some(Reason::PartialGroup { len: group.len(), group_size })
+        } else if problems.len() > problems_before {
+            None
+        } else {
+            let badge = group_items(group);
+            match badge.len() {
+                0 => Some(Reason::NoBadge),
+                1 => None,
+                _ => Some(Reason::MultipleBadges(badge.priorities().collect())),
+            }
+        };
+
+        if let Some(reason) = reason {
+            problems.push(Problem { line_number: first_line_number, line: group[0].to_string(), reason });
+        }
+    }
+
+    problems
+}
+
+/// Like `part1`, but invalid lines are either skipped or make the whole solve fail with every problem found
+pub fn checked_part1(input: &str, compartments: usize, on_invalid: OnInvalid) -> Result<u32, Vec<Problem>> {
+    let problems = validate(input, compartments);
+    if on_invalid == OnInvalid::Fail && !problems.is_empty() {
+        return Err(problems);
+    }
+
+    let invalid_lines = problems.iter().map(|p| p.line_number).collect::<HashSet<_>>();
+    let sum = input
+        .lines()
+        .enumerate()
+        .filter(|(i, _)| !invalid_lines.contains(&(i + 1)))
+        .map(|(_, line)| super::compartment_items(line, compartments).unwrap())
+        .flat_map(|items: Rucksack| items.priorities())
+        .sum();
+
+    Ok(sum)
+}
+
+/// Like `part2`, but groups with problems are either skipped or make the whole solve fail with every problem found
+pub fn checked_part2(input: &str, group_size: usize, on_invalid: OnInvalid) -> Result<u32, Vec<Problem>> {
+    let problems = validate_groups(input, group_size);
+    if on_invalid == OnInvalid::Fail && !problems.is_empty() {
+        return Err(problems);
+    }
+
+    let invalid_groups = problems.iter().map(|p| (p.line_number - 1) / group_size).collect::<HashSet<_>>();
+    let sum = input
+        .lines()
+        .collect::<Vec<_>>()
+        .chunks(group_size)
+        .enumerate()
+        .filter(|(i, _)| !invalid_groups.contains(i))
+        .flat_map(|(_, group)| group_items(group).priorities())
+        .sum();
+
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod day3_validation_tests {
+    use super::*;
+
+    const INPUT: &str = concat!(
+        "vJrwpWtwJgWrhcsFMMfFFhFp\n",
+        "abc\n",
+        "ab1a\n",
+        "abcd\n",
+        "abab\n",
+        "ttgJtRGJQctTZtZT\n",
+    );
+
+    #[test]
+    fn reports_every_problem() {
+        let problems = validate(INPUT, 2);
+
+        let reasons = problems.iter().map(|p| (p.line_number, p.reason.clone())).collect::<Vec<_>>();
+        assert_eq!(reasons, vec![
+            (2, Reason::UnevenLength { len: 3, compartments: 2 }),
+            (3, Reason::InvalidCharacter('1')),
+            (4, Reason::NoCommonItem),
+            (5, Reason::MultipleCommonItems(vec![1, 2])),
+        ]);
+    }
+
+    #[test]
+    fn skip_or_fail() {
+        // p (16) + t (20)
+        assert_eq!(checked_part1(INPUT, 2, OnInvalid::Skip), Ok(36));
+        assert_eq!(checked_part1(INPUT, 2, OnInvalid::Fail).unwrap_err().len(), 4);
+    }
+
+    #[test]
+    fn groups() {
+        let input = concat!(
+            "abX
+", "Xcd
+", "eXf
+",
+            "ab1
+", "abc
+", "abd
+",
+            "abc
+", "abd
+", "abe
+",
+            "xyz
+", "xyw
+", "xy
+",
+            "Zq
+", "Zr
+",
+        );
+
+        let reasons = validate_groups(input, 3).into_iter().map(|p| (p.line_number, p.reason)).collect::<Vec<_>>();
+        assert_eq!(reasons, vec![
+            (4, Reason::InvalidCharacter('1')),
+            (7, Reason::MultipleBadges(vec![1, 2])),
+            (10, Reason::MultipleBadges(vec![24, 25])),
+            (13, Reason::PartialGroup { len: 2, group_size: 3 }),
+        ]);
+
+        // Only the first group is valid, with badge X (50)
+        assert_eq!(checked_part2(input, 3, OnInvalid::Skip), Ok(50));
+        assert_eq!(checked_part2(input, 3, OnInvalid::Fail).unwrap_err().len(), 4);
+        assert_eq!(checked_part2("ab
+cd
+", 2, OnInvalid::Fail).unwrap_err()[0].to_string(),
+                   "line 1 'ab': no item common to every rucksack in the group");
+    }
+}
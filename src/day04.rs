@@ -1,9 +1,14 @@
+use std::fmt::Display;
+use itertools::Itertools;
+
+use crate::interval::{Interval, IntervalSet};
+
 #[aoc(day4, part1)]
 pub fn part1(input: &str) -> usize {
     input
         .lines()
         .map(get_bounds)
-        .filter(has_contain)
+        .filter(|bounds| has_contain(bounds))
         .count()
 }
 
@@ -12,62 +17,247 @@ pub fn part2(input: &str) -> usize {
     input
         .lines()
         .map(get_bounds)
-        .filter(has_overlap)
+        .filter(|bounds| has_overlap(bounds))
         .count()
 }
 
 
-fn get_bounds(line: &str) -> [i32; 4] {
+/// Every comma separated range on a line, there's usually two but there can be any number
+fn get_bounds(line: &str) -> Vec<Interval<i32>> {
     line
-        .split(&[',', '-'])
-        .map(|s| s.parse::<i32>().unwrap())
-        .collect::<Vec<i32>>()[..4]
-        .try_into()
-        .unwrap()
+        .split(',')
+        .map(|range| range.parse().unwrap())
+        .collect()
+}
+
+fn has_contain(bounds: &[Interval<i32>]) -> bool {
+    bounds
+        .iter()
+        .tuple_combinations()
+        .any(|(elf0, elf1)| elf0.contains(elf1) || elf1.contains(elf0))
+}
+
+fn has_overlap(bounds: &[Interval<i32>]) -> bool {
+    bounds
+        .iter()
+        .tuple_combinations()
+        .any(|(elf0, elf1)| elf0.overlaps(elf1))
+}
+
+/// How every range on a line relates to every other one
+#[derive(Debug)]
+pub struct LineReport {
+    pub ranges: Vec<Interval<i32>>,
+    /// `contains[i][j]` is whether range `i` contains range `j`
+    pub contains: Vec<Vec<bool>>,
+    /// `overlaps[i][j]` is whether range `i` overlaps range `j`
+    pub overlaps: Vec<Vec<bool>>,
+    /// Index of a range that contains every other range
+    pub container: Option<usize>,
+}
+
+impl LineReport {
+    pub fn new(line: &str) -> Self {
+        let ranges = get_bounds(line);
+
+        let matrix = |f: fn(&Interval<i32>, &Interval<i32>) -> bool| {
+            ranges
+                .iter()
+                .map(|a| ranges.iter().map(|b| f(a, b)).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        };
+        let contains = matrix(Interval::contains);
+        let overlaps = matrix(Interval::overlaps);
+
+        let container = contains.iter().position(|row| row.iter().all(|c| *c));
+
+        Self { ranges, contains, overlaps, container }
+    }
 }
 
-fn has_contain(bounds: &[i32; 4]) -> bool {
-    let [elf0_start, elf0_end, elf1_start, elf1_end] = bounds;
-    let elf0_sectors = elf0_end - elf0_start;
-    let elf1_sectors = elf1_end - elf1_start;
+impl Display for LineReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let header = self.ranges.iter().map(|r| format!("{r:>8}")).join("");
 
-    if elf0_sectors > elf1_sectors { // Elf 0 could contain elf1
-        elf0_start <= elf1_start && elf0_end >= elf1_end
-    } else { // Elf 1 could contain Elf 0
-        elf1_start <= elf0_start && elf1_end >= elf0_end
+        for (title, matrix) in [("contains", &self.contains), ("overlaps", &self.overlaps)] {
+            writeln!(f, "{title:>8}{header}")?;
+            for (range, row) in self.ranges.iter().zip(matrix) {
+                let cells = row.iter().map(|b| format!("{:>8}", if *b { "x" } else { "." })).join("");
+                writeln!(f, "{range:>8}{cells}")?;
+            }
+        }
+
+        match self.container {
+            Some(i) => write!(f, "{} contains all others", self.ranges[i]),
+            None => write!(f, "no range contains all others"),
+        }
     }
 }
 
-fn has_overlap(bounds: &[i32; 4]) -> bool {
-    let [elf0_start, elf0_end, elf1_start, elf1_end] = bounds;
-    
-    let elf0_start_inside_elf1 = elf0_start >= elf1_start && elf0_start <= elf1_end;
-    let elf0_end_inside_elf1 = elf0_end >= elf1_start && elf0_end <= elf1_end;
-    let elf1_start_inside_elf0 = elf1_start >= elf0_start && elf1_start <= elf0_end;
-    let elf1_end_inside_elf0 = elf1_end >= elf0_start && elf1_end <= elf0_end;
+/// Every elf's assignment, in input order
+pub fn assignments(input: &str) -> Vec<Interval<i32>> {
+    input
+        .lines()
+        .flat_map(get_bounds)
+        .collect()
+}
+
+/// Every section assigned to at least one elf
+pub fn coverage(assignments: &[Interval<i32>]) -> IntervalSet<i32> {
+    assignments.iter().copied().collect()
+}
+
+/// Sections between the lowest and highest assigned ones that nobody is assigned to
+pub fn unassigned(assignments: &[Interval<i32>]) -> IntervalSet<i32> {
+    coverage(assignments).gaps()
+}
 
-    elf0_start_inside_elf1 || elf0_end_inside_elf1 || elf1_start_inside_elf0 || elf1_end_inside_elf0
+/// Indexes of the elves assigned to `section`
+pub fn covering_elves(assignments: &[Interval<i32>], section: i32) -> Vec<usize> {
+    assignments
+        .iter()
+        .positions(|a| a.contains_point(section))
+        .collect()
+}
+
+/// The lowest section covered by the most elves, and how many elves cover it
+pub fn most_redundant(assignments: &[Interval<i32>]) -> Option<(i32, usize)> {
+    // Sweep over the first and last section of each assignment. Starts sort before ends at the same
+    // section as both ends are inclusive, and this way nothing steps past `i32::MAX`
+    let events = assignments
+        .iter()
+        .flat_map(|a| [(a.start(), 1), (a.end(), -1)])
+        .sorted_by_key(|(section, delta)| (*section, -*delta));
+
+    let mut best: Option<(i32, usize)> = None;
+    let mut count = 0_i32;
+    for (section, delta) in events {
+        count += delta;
+        if best.is_none_or(|(_, most)| count as usize > most) {
+            best = Some((section, count as usize));
+        }
+    }
+
+    best
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::interval::IntervalTree;
+
+    fn bounds([elf0_start, elf0_end, elf1_start, elf1_end]: [i32; 4]) -> Vec<Interval<i32>> {
+        vec![Interval::new(elf0_start, elf0_end), Interval::new(elf1_start, elf1_end)]
+    }
 
     #[test]
     fn test_has_overlap() {
-        assert!(!has_overlap(&[1, 2, 3, 4]));
-        assert!(!has_overlap(&[3, 4, 1, 2]));
-        assert!(!has_overlap(&[1, 1, 2, 2]));
-        assert!(!has_overlap(&[2, 2, 1, 1]));
-        assert!(has_overlap(&[1, 1, 1, 1]));
-        assert!(has_overlap(&[1, 10, 5, 5]));
-        assert!(has_overlap(&[5, 5, 1, 10]));
-        assert!(has_overlap(&[1, 2, 2, 3]));
-        assert!(has_overlap(&[2, 3, 1, 2]));
-        assert!(has_overlap(&[1, 3, 2, 4]));
-        assert!(has_overlap(&[2, 4, 1, 3]));
-        assert!(has_overlap(&[1, 4, 2, 3]));
-        assert!(has_overlap(&[2, 3, 1, 4]));
+        assert!(!has_overlap(&bounds([1, 2, 3, 4])));
+        assert!(!has_overlap(&bounds([3, 4, 1, 2])));
+        assert!(!has_overlap(&bounds([1, 1, 2, 2])));
+        assert!(!has_overlap(&bounds([2, 2, 1, 1])));
+        assert!(has_overlap(&bounds([1, 1, 1, 1])));
+        assert!(has_overlap(&bounds([1, 10, 5, 5])));
+        assert!(has_overlap(&bounds([5, 5, 1, 10])));
+        assert!(has_overlap(&bounds([1, 2, 2, 3])));
+        assert!(has_overlap(&bounds([2, 3, 1, 2])));
+        assert!(has_overlap(&bounds([1, 3, 2, 4])));
+        assert!(has_overlap(&bounds([2, 4, 1, 3])));
+        assert!(has_overlap(&bounds([1, 4, 2, 3])));
+        assert!(has_overlap(&bounds([2, 3, 1, 4])));
+    }
+
+    #[test]
+    fn sample_input() {
+        let input = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
+        assert_eq!(part1(input), 2);
+        assert_eq!(part2(input), 4);
+    }
+
+    #[test]
+    fn section_queries() {
+        let input = "2-4,6-8\n3-3,6-7\n12-14,11-11";
+        let assignments = assignments(input);
+
+        assert_eq!(unassigned(&assignments).intervals(), &[Interval::new(5, 5), Interval::new(9, 10)]);
+        assert_eq!(coverage(&assignments).coverage(), 10);
+        assert_eq!(covering_elves(&assignments, 6), vec![1, 3]);
+        assert_eq!(covering_elves(&assignments, 5), Vec::<usize>::new());
+        assert_eq!(most_redundant(&assignments), Some((3, 2)));
+    }
+
+    #[test]
+    fn most_redundant_near_type_bounds() {
+        let assignments = [Interval::new(1, i32::MAX), Interval::new(i32::MAX, i32::MAX), Interval::new(i32::MIN, 1)];
+        assert_eq!(most_redundant(&assignments), Some((1, 2)));
+
+        let assignments = [Interval::new(i32::MAX - 1, i32::MAX), Interval::new(i32::MAX, i32::MAX)];
+        assert_eq!(most_redundant(&assignments), Some((i32::MAX, 2)));
+    }
+
+    #[test]
+    fn lines_with_more_than_two_elves() {
+        assert!(has_contain(&get_bounds("1-2,5-6,5-5")));
+        assert!(!has_contain(&get_bounds("1-2,4-6,5-7")));
+        assert!(has_overlap(&get_bounds("1-2,4-6,5-7")));
+        assert!(!has_overlap(&get_bounds("1-2,4-4,6-7")));
+        assert_eq!(part1("1-2,5-6,5-5\n1-2,4-6,5-7"), 1);
+    }
+
+    #[test]
+    fn line_report() {
+        let report = LineReport::new("1-9,2-3,3-5");
+
+        assert_eq!(report.contains, vec![
+            vec![true, true, true],
+            vec![false, true, false],
+            vec![false, false, true],
+        ]);
+        assert_eq!(report.overlaps[1], vec![true, true, true]);
+        assert_eq!(report.container, Some(0));
+
+        assert_eq!(LineReport::new("1-3,2-9").container, None);
+    }
+
+    #[test]
+    fn interval_tree_agrees_with_has_overlap() {
+        // Plenty of generated assignments, from a simple LCG so the test is deterministic
+        let mut state = 12345_u64;
+        let mut next = |max: u64| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((state >> 33) % max) as i32
+        };
+        let input = (0..500)
+            .map(|_| {
+                let (a, b) = (next(1000), next(1000));
+                let (c, d) = (next(1000), next(1000));
+                format!("{}-{},{}-{}", a.min(b), a.max(b), c.min(d), c.max(d))
+            })
+            .join("\n");
+
+        let assignments = assignments(&input);
+        let tree = IntervalTree::new(&assignments);
+
+        let expected_pairs = (0..assignments.len())
+            .tuple_combinations()
+            .filter(|(i, j)| has_overlap(&[assignments[*i], assignments[*j]]))
+            .collect::<Vec<_>>();
+        assert_eq!(tree.overlapping_pairs(), expected_pairs);
+
+        for query in assignments.iter().take(50) {
+            let expected = assignments
+                .iter()
+                .positions(|a| has_overlap(&[*a, *query]))
+                .collect::<Vec<_>>();
+            assert_eq!(tree.overlapping(query), expected);
+            assert_eq!(tree.count_overlapping(query), expected.len());
+        }
+
+        // Every pair on a line that overlaps shows up in the tree's pairs
+        let overlapping_lines = input.lines().map(get_bounds).filter(|b| has_overlap(b)).count();
+        let pairs_within_lines = expected_pairs.iter().filter(|(i, j)| i / 2 == j / 2).count();
+        assert_eq!(pairs_within_lines, overlapping_lines);
+        assert_eq!(part2(&input), overlapping_lines);
     }
 }
\ No newline at end of file
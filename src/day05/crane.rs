@@ -0,0 +1,197 @@
+use super::dock::{Dock, DockError, Move};
+
+pub trait Crane {
+    fn name(&self) -> String;
+
+    /// Carries out a whole move. A move that can't be made fails without touching the dock
+    fn perform(&mut self, dock: &mut Dock, mv: &Move) -> Result<(), DockError>;
+
+    /// Carries out a move backwards, so crates end up how they were before `perform`
+    fn reverse(&mut self, dock: &mut Dock, mv: &Move) -> Result<(), DockError>;
+
+    /// Number of lifts performed so far
+    fn lifts(&self) -> usize;
+}
+
+/// Picks up one crate at a time
+#[derive(Debug, Default)]
+pub struct CrateMover9000 {
+    lifts: usize,
+}
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        "9000".to_string()
+    }
+
+    fn perform(&mut self, dock: &mut Dock, mv: &Move) -> Result<(), DockError> {
+        dock.apply(mv)?;
+        self.lifts += mv.num_crates;
+        Ok(())
+    }
+
+    fn reverse(&mut self, dock: &mut Dock, mv: &Move) -> Result<(), DockError> {
+        self.perform(dock, &mv.inverse())
+    }
+
+    fn lifts(&self) -> usize {
+        self.lifts
+    }
+}
+
+/// Picks up every crate in a move at once
+#[derive(Debug, Default)]
+pub struct CrateMover9001 {
+    lifts: usize,
+}
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        "9001".to_string()
+    }
+
+    fn perform(&mut self, dock: &mut Dock, mv: &Move) -> Result<(), DockError> {
+        dock.apply_in_order(mv)?;
+        if mv.num_crates > 0 {
+            self.lifts += 1;
+        }
+        Ok(())
+    }
+
+    fn reverse(&mut self, dock: &mut Dock, mv: &Move) -> Result<(), DockError> {
+        self.perform(dock, &mv.inverse())
+    }
+
+    fn lifts(&self) -> usize {
+        self.lifts
+    }
+}
+
+/// Picks up at most `capacity` crates at once, keeping their order within each lift
+#[derive(Debug)]
+pub struct LimitedCapacity {
+    capacity: usize,
+    lifts: usize,
+}
+
+impl LimitedCapacity {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "Crane needs to be able to lift at least 1 crate");
+        Self { capacity, lifts: 0 }
+    }
+}
+
+impl Crane for LimitedCapacity {
+    fn name(&self) -> String {
+        format!("limited-{}", self.capacity)
+    }
+
+    fn perform(&mut self, dock: &mut Dock, mv: &Move) -> Result<(), DockError> {
+        dock.check(mv)?;
+
+        let mut remaining = mv.num_crates;
+        while remaining > 0 {
+            let num_crates = remaining.min(self.capacity);
+            dock.apply_in_order(&Move { num_crates, ..*mv })?;
+            remaining -= num_crates;
+            self.lifts += 1;
+        }
+
+        Ok(())
+    }
+
+    fn reverse(&mut self, dock: &mut Dock, mv: &Move) -> Result<(), DockError> {
+        let inverse = mv.inverse();
+        dock.check(&inverse)?;
+
+        // Undo the lifts last first, the last lift is the only one that can be smaller than capacity
+        let mut remaining = mv.num_crates;
+        let mut num_crates = match mv.num_crates % self.capacity {
+            0 => self.capacity,
+            partial => partial,
+        };
+        while remaining > 0 {
+            dock.apply_in_order(&Move { num_crates, ..inverse })?;
+            remaining -= num_crates;
+            num_crates = self.capacity;
+            self.lifts += 1;
+        }
+
+        Ok(())
+    }
+
+    fn lifts(&self) -> usize {
+        self.lifts
+    }
+}
+
+/// Looks up a crane by the name it reports, `9000`, `9001` or `limited-K`
+pub fn crane_by_name(name: &str) -> Option<Box<dyn Crane>> {
+    match name {
+        "9000" => Some(Box::<CrateMover9000>::default()),
+        "9001" => Some(Box::<CrateMover9001>::default()),
+        _ => {
+            let capacity = name.strip_prefix("limited-")?.parse::<usize>().ok()?;
+            if capacity == 0 {
+                return None;
+            }
+            Some(Box::new(LimitedCapacity::new(capacity)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod day5_crane_tests {
+    use super::*;
+
+    fn dock() -> Dock {
+        "[A]        \n[B]        \n[C]        \n[D]        \n[E]     [F]\n 1   2   3 ".parse().unwrap()
+    }
+
+    #[test]
+    fn lift_counts() {
+        let mv = Move { num_crates: 5, from_index: 0, to_index: 1 };
+
+        for (name, lifts, top) in [("9000", 5, "EF"), ("9001", 1, "AF"), ("limited-2", 3, "EF")] {
+            let mut crane = crane_by_name(name).unwrap();
+            let mut d = dock();
+            crane.perform(&mut d, &mv).unwrap();
+
+            assert_eq!(crane.name(), name);
+            assert_eq!(crane.lifts(), lifts, "{name}");
+            assert_eq!(d.top_message(), top, "{name}");
+        }
+    }
+
+    #[test]
+    fn limited_capacity_keeps_order_within_lift() {
+        let mut crane = LimitedCapacity::new(2);
+        let mut d = dock();
+        crane.perform(&mut d, &Move { num_crates: 5, from_index: 0, to_index: 2 }).unwrap();
+
+        // Lifts of [B, A], [D, C], then [E]
+        assert_eq!(d.stacks()[2], vec!["F", "B", "A", "D", "C", "E"]);
+    }
+
+    #[test]
+    fn reverse_undoes_perform() {
+        let mv = Move { num_crates: 5, from_index: 0, to_index: 2 };
+
+        for name in ["9000", "9001", "limited-2", "limited-5", "limited-7"] {
+            let mut crane = crane_by_name(name).unwrap();
+            let mut d = dock();
+            crane.perform(&mut d, &mv).unwrap();
+            assert_ne!(d, dock());
+
+            crane.reverse(&mut d, &mv).unwrap();
+            assert_eq!(d, dock(), "{name}");
+        }
+    }
+
+    #[test]
+    fn unknown_cranes() {
+        assert!(crane_by_name("9002").is_none());
+        assert!(crane_by_name("limited-0").is_none());
+        assert!(crane_by_name("limited-x").is_none());
+    }
+}
@@ -0,0 +1,433 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use itertools::Itertools;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub num_crates: usize,
+    pub from_index: usize,
+    pub to_index: usize,
+}
+
+impl FromStr for Move {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = s.split_whitespace().collect::<Vec<&str>>();
+
+        let number = |i: usize| {
+            tokens
+                .get(i)
+                .and_then(|t| t.parse::<usize>().ok())
+                .ok_or(format!("Unable to parse move '{s}'"))
+        };
+        let index = |i: usize| {
+            number(i)?
+                .checked_sub(1)
+                .ok_or(format!("Stacks in move '{s}' are numbered from 1"))
+        };
+
+        Ok(Move {
+            num_crates: number(1)?,
+            from_index: index(3)?,
+            to_index: index(5)?,
+        })
+    }
+}
+
+impl Move {
+    /// Move that puts the crates back where they came from
+    pub fn inverse(&self) -> Move {
+        Move { num_crates: self.num_crates, from_index: self.to_index, to_index: self.from_index }
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "move {} from {} to {}", self.num_crates, self.from_index + 1, self.to_index + 1)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DockError {
+    StackOutOfRange { index: usize, num_stacks: usize },
+    EmptyStack { index: usize },
+}
+
+impl Display for DockError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DockError::StackOutOfRange { index, num_stacks } => write!(f, "stack {} doesn't exist, there are {num_stacks} stacks", index + 1),
+            DockError::EmptyStack { index } => write!(f, "stack {} ran out of crates", index + 1),
+        }
+    }
+}
+
+/// A move made directly on the dock, kept so it can be undone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub mv: Move,
+    /// Whether the crates were moved all at once
+    pub in_order: bool,
+}
+
+/// Stacks of crates, bottom crate first, and the log of steps made on them.
+/// Two docks are equal if their stacks are, regardless of how they got there
+#[derive(Debug, Clone)]
+pub struct Dock {
+    stacks: Vec<Vec<String>>,
+    done: Vec<Step>,
+    undone: Vec<Step>,
+}
+
+impl PartialEq for Dock {
+    fn eq(&self, other: &Self) -> bool {
+        self.stacks == other.stacks
+    }
+}
+
+impl Eq for Dock {}
+
+impl Dock {
+    pub fn new(stacks: Vec<Vec<String>>) -> Self {
+        Self { stacks, done: vec![], undone: vec![] }
+    }
+
+    pub fn stacks(&self) -> &[Vec<String>] {
+        &self.stacks
+    }
+
+    /// Checks a move can be made without changing anything, so a failed move leaves the dock as it was
+    pub fn check(&self, mv: &Move) -> Result<(), DockError> {
+        for index in [mv.from_index, mv.to_index] {
+            if index >= self.stacks.len() {
+                return Err(DockError::StackOutOfRange { index, num_stacks: self.stacks.len() });
+            }
+        }
+
+        if self.stacks[mv.from_index].len() < mv.num_crates {
+            return Err(DockError::EmptyStack { index: mv.from_index });
+        }
+
+        Ok(())
+    }
+
+    /// Moves crates one at a time, so they end up in reverse order
+    pub fn apply(&mut self, mv: &Move) -> Result<(), DockError> {
+        self.step(Step { mv: *mv, in_order: false })
+    }
+
+    /// Moves crates all at once, so they keep their order
+    pub fn apply_in_order(&mut self, mv: &Move) -> Result<(), DockError> {
+        self.step(Step { mv: *mv, in_order: true })
+    }
+
+    fn step(&mut self, step: Step) -> Result<(), DockError> {
+        self.move_crates(&step)?;
+        self.done.push(step);
+        self.undone.clear();
+        Ok(())
+    }
+
+    fn move_crates(&mut self, Step { mv, in_order }: &Step) -> Result<(), DockError> {
+        self.check(mv)?;
+
+        if *in_order {
+            let from_stack = &mut self.stacks[mv.from_index];
+            let crates_start = from_stack.len() - mv.num_crates;
+            let crates_to_move = from_stack.split_off(crates_start);
+            self.stacks[mv.to_index].extend(crates_to_move);
+        } else {
+            for _ in 0..mv.num_crates {
+                let crate_to_move = self.stacks[mv.from_index].pop().unwrap();
+                self.stacks[mv.to_index].push(crate_to_move);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Steps made so far, oldest first. Every `apply` or `apply_in_order` is one step, so it depends on
+    /// the crane: the 9000 makes one step per move however many crates it lifts, a limited capacity
+    /// crane one per lift
+    pub fn history(&self) -> &[Step] {
+        &self.done
+    }
+
+    /// Reverts the last step, returning it, or `None` if there's nothing to undo
+    pub fn undo(&mut self) -> Option<Step> {
+        let step = self.done.pop()?;
+        // Reversing a step that was just made can't fail
+        self.move_crates(&Step { mv: step.mv.inverse(), ..step }).unwrap();
+        self.undone.push(step);
+        Some(step)
+    }
+
+    /// Makes the last undone step again, returning it, or `None` if there's nothing to redo
+    pub fn redo(&mut self) -> Option<Step> {
+        let step = self.undone.pop()?;
+        self.move_crates(&step).unwrap();
+        self.done.push(step);
+        Some(step)
+    }
+
+    /// Top crate of each stack. Empty stacks don't contribute anything
+    pub fn top_message(&self) -> String {
+        self.stacks
+            .iter()
+            .filter_map(|stack| stack.last())
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+impl FromStr for Dock {
+    type Err = String;
+
+    /// Parses the stack diagram, bottom line is the stack numbers. Crates are put on whichever stack's
+    /// label is closest to the middle of the crate, so there can be any number of stacks, crates can
+    /// have labels longer than a character, and lines don't need trailing spaces
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().rev();
+
+        let label_line = lines.next().ok_or("Stack diagram is empty")?;
+        let labels = tokens(label_line);
+
+        for (i, (_, _, label)) in labels.iter().enumerate() {
+            if label.parse::<usize>() != Ok(i + 1) {
+                return Err(format!("Expected stack label {} but found '{label}'", i + 1));
+            }
+        }
+
+        let mut stacks = vec![vec![] as Vec<String>; labels.len()];
+
+        for line in lines {
+            for (start, end, token) in tokens(line) {
+                let name = token
+                    .strip_prefix('[')
+                    .and_then(|t| t.strip_suffix(']'))
+                    .filter(|name| !name.is_empty())
+                    .ok_or(format!("Expected a crate like '[A]' but found '{token}'"))?;
+
+                // Doubled so the middle of a crate or label is always a whole number
+                let middle = start + end;
+                let (index, _) = labels
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (label_start, label_end, _))| *label_start <= end && start <= *label_end)
+                    .min_by_key(|(_, (label_start, label_end, _))| middle.abs_diff(label_start + label_end))
+                    .ok_or(format!("Crate '{token}' isn't above any stack label"))?;
+
+                stacks[index].push(name.to_string());
+            }
+        }
+
+        Ok(Dock::new(stacks))
+    }
+}
+
+impl Display for Dock {
+    /// Draws the dock like the puzzle does, every column is as wide as its widest crate or label
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let labels = (1..=self.stacks.len()).map(|i| i.to_string()).collect::<Vec<_>>();
+        let widths = self.stacks
+            .iter()
+            .zip(&labels)
+            .map(|(stack, label)| {
+                let widest_crate = stack.iter().map(|c| c.chars().count() + 2).max().unwrap_or(3);
+                widest_crate.max(label.len())
+            })
+            .collect::<Vec<_>>();
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+
+        let centered = |s: &str, width: usize| {
+            let left = (width - s.chars().count()).div_ceil(2);
+            format!("{}{s:<w$}", " ".repeat(left), w = width - left)
+        };
+
+        for row in (0..height).rev() {
+            let line = self.stacks
+                .iter()
+                .zip(&widths)
+                .map(|(stack, width)| match stack.get(row) {
+                    Some(c) => centered(&format!("[{c}]"), *width),
+                    None => " ".repeat(*width),
+                })
+                .join(" ");
+            writeln!(f, "{line}")?;
+        }
+
+        let label_line = labels.iter().zip(&widths).map(|(label, width)| centered(label, *width)).join(" ");
+        write!(f, "{label_line}")
+    }
+}
+
+/// Whitespace separated tokens, with the (inclusive) columns they start and end at
+fn tokens(line: &str) -> Vec<(usize, usize, String)> {
+    let mut tokens = vec![];
+    let mut current: Option<(usize, String)> = None;
+
+    for (col, ch) in line.chars().enumerate() {
+        match (!ch.is_whitespace(), &mut current) {
+            (true, Some((_, token))) => token.push(ch),
+            (true, None) => current = Some((col, ch.to_string())),
+            (false, Some(_)) => {
+                let (start, token) = current.take().unwrap();
+                tokens.push((start, col - 1, token));
+            },
+            (false, None) => {},
+        }
+    }
+    if let Some((start, token)) = current {
+        let end = start + token.chars().count() - 1;
+        tokens.push((start, end, token));
+    }
+
+    tokens
+}
+
+/// Splits the puzzle input into the starting dock and the moves
+pub fn parse(input: &str) -> Result<(Dock, Vec<Move>), String> {
+    let (diagram, moves) = input.split_once("\n\n").ok_or("Input is missing the blank line between stacks and moves")?;
+
+    let dock = diagram.parse::<Dock>()?;
+    let moves = moves.lines().map(str::parse).collect::<Result<Vec<Move>, _>>()?;
+
+    Ok((dock, moves))
+}
+
+#[cfg(test)]
+mod day5_dock_tests {
+    use super::*;
+
+    fn dock() -> Dock {
+        "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 ".parse().unwrap()
+    }
+
+    #[test]
+    fn parse_dock() {
+        let stacks = dock().stacks().to_vec();
+        assert_eq!(stacks, vec![vec!["Z", "N"], vec!["M", "C", "D"], vec!["P"]]);
+    }
+
+    #[test]
+    fn parse_ragged_dock() {
+        let d = "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3".parse::<Dock>().unwrap();
+        assert_eq!(d, dock());
+    }
+
+    #[test]
+    fn parse_wide_dock() {
+        let diagram = [
+            "                                        [K]",
+            "[A]                                 [J] [L]",
+            "[B] [C] [D] [E] [F] [G] [H] [I] [X] [Y] [Z]",
+            " 1   2   3   4   5   6   7   8   9  10  11",
+        ].join("\n");
+        let d = diagram.parse::<Dock>().unwrap();
+
+        assert_eq!(d.stacks().len(), 11);
+        assert_eq!(d.stacks()[0], vec!["B", "A"]);
+        assert_eq!(d.stacks()[9], vec!["Y", "J"]);
+        assert_eq!(d.stacks()[10], vec!["Z", "L", "K"]);
+    }
+
+    #[test]
+    fn parse_multi_character_crates() {
+        let diagram = [
+            "[AB]",
+            "[CD]  [EFG]",
+            "[H]    [I]   [JK]",
+            " 1      2     3",
+        ].join("\n");
+        let d = diagram.parse::<Dock>().unwrap();
+
+        assert_eq!(d.stacks(), &[vec!["H", "CD", "AB"], vec!["I", "EFG"], vec!["JK"]]);
+        assert_eq!(d.top_message(), "ABEFGJK");
+    }
+
+    #[test]
+    fn parse_bad_diagrams() {
+        assert!("[A]\n 2".parse::<Dock>().is_err());
+        assert!("[A]    [B]\n 1".parse::<Dock>().is_err());
+        assert!("A\n 1".parse::<Dock>().is_err());
+    }
+
+    #[test]
+    fn display_matches_puzzle() {
+        let diagram = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 ";
+        assert_eq!(dock().to_string(), diagram);
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let diagram = [
+            "[AB]",
+            "[CD]  [EFG]",
+            "[H]    [I]   [JK]",
+            " 1      2     3",
+        ].join("\n");
+        let d = diagram.parse::<Dock>().unwrap();
+        assert_eq!(d.to_string().parse::<Dock>().unwrap(), d);
+
+        let mut stacks = vec![vec![]; 11];
+        stacks[10].push("A".to_string());
+        let d = Dock::new(stacks);
+        assert_eq!(d.to_string().parse::<Dock>().unwrap(), d);
+        assert!(d.to_string().ends_with(" 9   10  11"));
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut d = dock();
+        d.apply(&Move { num_crates: 2, from_index: 1, to_index: 0 }).unwrap();
+        d.apply_in_order(&Move { num_crates: 3, from_index: 0, to_index: 2 }).unwrap();
+        let after = d.clone();
+
+        assert_eq!(d.history().len(), 2);
+        assert!(d.undo().unwrap().in_order);
+        assert!(!d.undo().unwrap().in_order);
+        assert_eq!(d.undo(), None);
+        assert_eq!(d, dock());
+
+        d.redo().unwrap();
+        d.redo().unwrap();
+        assert_eq!(d.redo(), None);
+        assert_eq!(d, after);
+
+        // A new step means there's nothing left to redo
+        d.undo();
+        d.apply(&Move { num_crates: 1, from_index: 0, to_index: 1 }).unwrap();
+        assert_eq!(d.redo(), None);
+    }
+
+    #[test]
+    fn parse_move() {
+        let mv = "move 3 from 1 to 3".parse::<Move>().unwrap();
+        assert_eq!(mv, Move { num_crates: 3, from_index: 0, to_index: 2 });
+        assert_eq!(mv.to_string(), "move 3 from 1 to 3");
+        assert!("move 3 from 0 to 3".parse::<Move>().is_err());
+        assert!("move x from 1 to 3".parse::<Move>().is_err());
+    }
+
+    #[test]
+    fn bad_moves_leave_dock_unchanged() {
+        let mut d = dock();
+
+        let err = d.apply(&Move { num_crates: 3, from_index: 0, to_index: 1 });
+        assert_eq!(err, Err(DockError::EmptyStack { index: 0 }));
+
+        let err = d.apply_in_order(&Move { num_crates: 1, from_index: 0, to_index: 3 });
+        assert_eq!(err, Err(DockError::StackOutOfRange { index: 3, num_stacks: 3 }));
+
+        assert_eq!(d, dock());
+    }
+
+    #[test]
+    fn top_message_skips_empty_stacks() {
+        let mut d = dock();
+        d.apply(&Move { num_crates: 1, from_index: 2, to_index: 0 }).unwrap();
+        assert_eq!(d.top_message(), "PD");
+    }
+}
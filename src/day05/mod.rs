@@ -0,0 +1,132 @@
+pub mod crane;
+use crane::{Crane, CrateMover9000, CrateMover9001, crane_by_name};
+
+pub mod dock;
+use dock::{parse, Dock, Move};
+
+pub mod replay;
+
+#[aoc(day5, part1)]
+pub fn part1(input: &str) -> String {
+    solve(input, &mut CrateMover9000::default()).unwrap()
+}
+
+#[aoc(day5, part2)]
+pub fn part2(input: &str) -> String {
+    solve(input, &mut CrateMover9001::default()).unwrap()
+}
+
+/// Rearranges the crates with `crane` and gets the message on top of the stacks
+pub fn solve(input: &str, crane: &mut dyn Crane) -> Result<String, String> {
+    let (mut dock, moves) = parse(input)?;
+
+    for mv in &moves {
+        crane
+            .perform(&mut dock, mv)
+            .map_err(|e| format!("Crane {} unable to {mv}: {e}", crane.name()))?;
+    }
+
+    Ok(dock.top_message())
+}
+
+/// Runs the procedure backwards from the final arrangement to recover the starting one
+pub fn unsolve(final_dock: &Dock, moves: &[Move], crane: &mut dyn Crane) -> Result<Dock, String> {
+    let mut dock = final_dock.clone();
+
+    for mv in moves.iter().rev() {
+        crane
+            .reverse(&mut dock, mv)
+            .map_err(|e| format!("Crane {} unable to reverse {mv}: {e}", crane.name()))?;
+    }
+
+    Ok(dock)
+}
+
+/// Whether running the procedure forwards then backwards with the crane named `crane_name` gets back the starting arrangement
+pub fn round_trips(input: &str, crane_name: &str) -> Result<bool, String> {
+    let (start, moves) = parse(input)?;
+    let mut crane = crane_by_name(crane_name).ok_or(format!("Unknown crane '{crane_name}'"))?;
+
+    let mut dock = start.clone();
+    for mv in &moves {
+        crane
+            .perform(&mut dock, mv)
+            .map_err(|e| format!("Crane {} unable to {mv}: {e}", crane.name()))?;
+    }
+
+    Ok(unsolve(&dock, &moves, crane.as_mut())? == start)
+}
+
+/// Like `solve` with the crane named `crane_name`, also giving the number of lifts it took
+pub fn solve_with(input: &str, crane_name: &str) -> Result<(String, usize), String> {
+    let mut crane = crane_by_name(crane_name).ok_or(format!("Unknown crane '{crane_name}'"))?;
+    let message = solve(input, crane.as_mut())?;
+
+    Ok((message, crane.lifts()))
+}
+
+#[cfg(test)]
+pub mod day5_tests {
+    use super::*;
+
+    pub const SAMPLE_INPUT: &str = concat!(
+        "    [D]    \n",
+        "[N] [C]    \n",
+        "[Z] [M] [P]\n",
+        " 1   2   3 \n",
+        "\n",
+        "move 1 from 2 to 1\n",
+        "move 3 from 1 to 3\n",
+        "move 2 from 2 to 1\n",
+        "move 1 from 1 to 2\n",
+    );
+
+    #[test]
+    fn part1_sample_input() {
+        let answer = part1(SAMPLE_INPUT);
+        assert_eq!(answer, "CMZ");
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let answer = part2(SAMPLE_INPUT);
+        assert_eq!(answer, "MCD");
+    }
+
+    #[test]
+    fn solve_with_named_cranes() {
+        assert_eq!(solve_with(SAMPLE_INPUT, "9000"), Ok(("CMZ".to_string(), 7)));
+        assert_eq!(solve_with(SAMPLE_INPUT, "9001"), Ok(("MCD".to_string(), 4)));
+        assert_eq!(solve_with(SAMPLE_INPUT, "limited-2"), Ok(("MCZ".to_string(), 5)));
+        assert!(solve_with(SAMPLE_INPUT, "9002").is_err());
+    }
+
+    #[test]
+    fn unsolve_recovers_start() {
+        let (start, moves) = parse(SAMPLE_INPUT).unwrap();
+
+        for name in ["9000", "9001", "limited-2"] {
+            assert_eq!(round_trips(SAMPLE_INPUT, name), Ok(true), "{name}");
+        }
+
+        // Final arrangement for the CrateMover 9001, written by hand
+        let final_dock = "        [D]\n        [N]\n        [Z]\n[M] [C] [P]\n 1   2   3".parse::<Dock>().unwrap();
+        let unsolved = unsolve(&final_dock, &moves, &mut CrateMover9001::default()).unwrap();
+        assert_eq!(unsolved, start);
+    }
+
+    #[test]
+    fn unsolve_rejects_impossible_final_state() {
+        let (_, moves) = parse(SAMPLE_INPUT).unwrap();
+        let final_dock = "[A] [B] [C]\n 1   2   3".parse::<Dock>().unwrap();
+
+        assert!(unsolve(&final_dock, &moves, &mut CrateMover9000::default()).is_err());
+    }
+
+    #[test]
+    fn solve_reports_failed_move() {
+        let input = SAMPLE_INPUT.replace("move 3 from 1 to 3", "move 4 from 1 to 3");
+        let err = solve(&input, &mut CrateMover9000::default()).unwrap_err();
+        assert_eq!(err, "Crane 9000 unable to move 4 from 1 to 3: stack 1 ran out of crates");
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+
+use super::crane::Crane;
+use super::dock::{Dock, Move};
+
+/// The dock after a number of moves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    /// Number of moves made so far
+    pub step: usize,
+    /// Move that produced this frame, `None` for the starting arrangement
+    pub mv: Option<Move>,
+    /// Only the stacks, without the steps that led there, so frames don't grow with the procedure
+    pub dock: Dock,
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.mv {
+            Some(mv) => writeln!(f, "step {}: {mv}", self.step)?,
+            None => writeln!(f, "step 0: start")?,
+        }
+        write!(f, "{}", self.dock)
+    }
+}
+
+/// Runs a procedure one move at a time, yielding the starting arrangement then the dock after every move.
+/// Stops after the first move that fails
+pub struct Replay<'a> {
+    dock: Dock,
+    moves: std::slice::Iter<'a, Move>,
+    crane: &'a mut dyn Crane,
+    step: usize,
+    done: bool,
+}
+
+impl<'a> Replay<'a> {
+    pub fn new(dock: Dock, moves: &'a [Move], crane: &'a mut dyn Crane) -> Self {
+        Self { dock, moves: moves.iter(), crane, step: 0, done: false }
+    }
+
+    fn frame(&self, mv: Option<Move>) -> Frame {
+        Frame { step: self.step, mv, dock: Dock::new(self.dock.stacks().to_vec()) }
+    }
+}
+
+impl Iterator for Replay<'_> {
+    type Item = Result<Frame, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let frame = if self.step == 0 {
+            self.frame(None)
+        } else {
+            let mv = *self.moves.next()?;
+            if let Err(e) = self.crane.perform(&mut self.dock, &mv) {
+                self.done = true;
+                return Some(Err(format!("Crane {} unable to {mv}: {e}", self.crane.name())));
+            }
+            self.frame(Some(mv))
+        };
+
+        self.step += 1;
+        Some(Ok(frame))
+    }
+}
+
+/// Every frame of a procedure, for exporting
+pub fn frames(dock: Dock, moves: &[Move], crane: &mut dyn Crane) -> Result<Vec<Frame>, String> {
+    Replay::new(dock, moves, crane).collect()
+}
+
+/// Writes every frame, separated by blank lines
+pub fn export(frames: &[Frame], output: &mut impl Write) -> io::Result<()> {
+    for (i, frame) in frames.iter().enumerate() {
+        if i > 0 {
+            writeln!(output)?;
+        }
+        writeln!(output, "{frame}")?;
+    }
+    Ok(())
+}
+
+/// Shows one frame at a time, moving on when a line is read from `input`. `q` stops early
+pub fn step_through(replay: Replay, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<()> {
+    for frame in replay {
+        match frame {
+            Ok(frame) => writeln!(output, "{frame}\n")?,
+            Err(e) => {
+                writeln!(output, "{e}")?;
+                break;
+            },
+        }
+
+        write!(output, "[enter] next move, [q] quit: ")?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 || line.trim() == "q" {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod day5_replay_tests {
+    use super::*;
+    use crate::day05::crane::CrateMover9000;
+    use crate::day05::day5_tests::SAMPLE_INPUT;
+    use crate::day05::dock::parse;
+
+    #[test]
+    fn frames_for_every_move() {
+        let (dock, moves) = parse(SAMPLE_INPUT).unwrap();
+        let frames = frames(dock, &moves, &mut CrateMover9000::default()).unwrap();
+
+        assert_eq!(frames.len(), 5);
+        assert_eq!(frames[1].to_string(), "step 1: move 1 from 2 to 1\n[D]        \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 ");
+        assert_eq!(frames.last().unwrap().dock.top_message(), "CMZ");        assert!(frames.iter().all(|frame| frame.dock.history().is_empty()));
+    }
+
+    #[test]
+    fn replay_stops_at_failed_move() {
+        let (dock, mut moves) = parse(SAMPLE_INPUT).unwrap();
+        moves[1].num_crates = 10;
+
+        let results = Replay::new(dock, &moves, &mut CrateMover9000::default()).collect::<Vec<_>>();
+        assert_eq!(results.len(), 3);
+        assert!(results[2].is_err());
+    }
+
+    #[test]
+    fn step_through_quits() {
+        let (dock, moves) = parse(SAMPLE_INPUT).unwrap();
+        let mut crane = CrateMover9000::default();
+        let mut output = vec![];
+
+        step_through(Replay::new(dock, &moves, &mut crane), &mut "\nq\n".as_bytes(), &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("step 1: move 1 from 2 to 1"));
+        assert!(!output.contains("step 2"));
+    }
+}
@@ -0,0 +1,179 @@
+use std::fmt::Display;
+use std::io::{self, Read};
+
+use super::detector::MarkerDetector;
+
+const PACKET_WINDOW: usize = 4;
+const MESSAGE_WINDOW: usize = 14;
+
+/// Something the device noticed in the signal. Offsets are the number of bytes received up to and
+/// including the end of the marker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    StartOfPacket(usize),
+    StartOfMessage(usize),
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Event::StartOfPacket(offset) => write!(f, "start-of-packet at offset {offset}"),
+            Event::StartOfMessage(offset) => write!(f, "start-of-message at offset {offset}"),
+        }
+    }
+}
+
+/// Receives the signal a chunk at a time, however it's split up, and reports the first start-of-packet
+/// and start-of-message markers as soon as they arrive
+pub struct Decoder {
+    packet: Option<MarkerDetector>,
+    message: Option<MarkerDetector>,
+    offset: usize,
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        Self::with_windows(PACKET_WINDOW, MESSAGE_WINDOW)
+    }
+}
+
+impl Decoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_windows(packet_window: usize, message_window: usize) -> Self {
+        Self {
+            packet: Some(MarkerDetector::new(packet_window)),
+            message: Some(MarkerDetector::new(message_window)),
+            offset: 0,
+        }
+    }
+
+    /// Number of bytes received so far
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Whether both markers have been found, so there's nothing left to look for
+    pub fn is_done(&self) -> bool {
+        self.packet.is_none() && self.message.is_none()
+    }
+
+    /// Takes the next chunk of the signal, returning any markers that ended in it
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<Event> {
+        let mut events = vec![];
+
+        // An empty window is already a marker before any bytes arrive, like `find_marker` says
+        if self.offset == 0 {
+            if self.packet.as_ref().is_some_and(MarkerDetector::is_marker) {
+                self.packet = None;
+                events.push(Event::StartOfPacket(0));
+            }
+            if self.message.as_ref().is_some_and(MarkerDetector::is_marker) {
+                self.message = None;
+                events.push(Event::StartOfMessage(0));
+            }
+        }
+
+        for b in chunk {
+            if self.is_done() {
+                break;
+            }
+
+            self.offset += 1;
+
+            // Each detector is dropped once its marker is found, we only care about the first one
+            if self.packet.as_mut().is_some_and(|d| d.push(*b)) {
+                self.packet = None;
+                events.push(Event::StartOfPacket(self.offset));
+            }
+            if self.message.as_mut().is_some_and(|d| d.push(*b)) {
+                self.message = None;
+                events.push(Event::StartOfMessage(self.offset));
+            }
+        }
+
+        events
+    }
+}
+
+/// Streams `reader` through a decoder, calling `on_event` as markers are found. Stops reading once both are
+pub fn decode(mut reader: impl Read, mut on_event: impl FnMut(Event)) -> io::Result<()> {
+    let mut decoder = Decoder::new();
+    let mut buf = [0; 4096];
+
+    while !decoder.is_done() {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        decoder.feed(&buf[..n]).into_iter().for_each(&mut on_event);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod day6_decoder_tests {
+    use super::*;
+    use crate::day06::detector::find_marker;
+    use crate::day06::{part1, part2};
+
+    const SAMPLE_INPUT: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+
+    #[test]
+    fn events_across_chunk_boundaries() {
+        for chunk_size in 1..=SAMPLE_INPUT.len() {
+            let mut decoder = Decoder::new();
+            let events = SAMPLE_INPUT
+                .as_bytes()
+                .chunks(chunk_size)
+                .flat_map(|chunk| decoder.feed(chunk))
+                .collect::<Vec<_>>();
+
+            assert_eq!(events, vec![Event::StartOfPacket(7), Event::StartOfMessage(19)], "chunk size {chunk_size}");
+            assert!(decoder.is_done());
+        }
+    }
+
+    #[test]
+    fn decode_reader() {
+        let mut events = vec![];
+        decode(SAMPLE_INPUT.as_bytes(), |e| events.push(e)).unwrap();
+
+        assert_eq!(events, vec![Event::StartOfPacket(part1(SAMPLE_INPUT)), Event::StartOfMessage(part2(SAMPLE_INPUT))]);
+        assert_eq!(events[0].to_string(), "start-of-packet at offset 7");
+    }
+
+    #[test]
+    fn incomplete_signal() {
+        let mut decoder = Decoder::new();
+        assert_eq!(decoder.feed(b"abab"), vec![]);
+        assert_eq!(decoder.feed(b"cd"), vec![Event::StartOfPacket(6)]);
+        assert!(!decoder.is_done());
+        assert_eq!(decoder.offset(), 6);
+    }
+
+    #[test]
+    fn empty_windows() {
+        let bytes = SAMPLE_INPUT.as_bytes();
+        for (packet_window, message_window) in [(0, 4), (4, 0), (0, 0)] {
+            let mut decoder = Decoder::with_windows(packet_window, message_window);
+            let mut events = decoder.feed(bytes);
+            events.sort_by_key(|e| matches!(e, Event::StartOfMessage(_)));
+
+            assert_eq!(events, vec![
+                Event::StartOfPacket(find_marker(bytes, packet_window).unwrap()),
+                Event::StartOfMessage(find_marker(bytes, message_window).unwrap()),
+            ]);
+        }
+
+        let mut decoder = Decoder::with_windows(0, 0);
+        assert_eq!(decoder.feed(b""), vec![Event::StartOfPacket(0), Event::StartOfMessage(0)]);
+        assert_eq!(decoder.offset(), 0);
+    }
+}
@@ -0,0 +1,169 @@
+use std::io::{self, BufReader, Read};
+
+use super::symbols::Detector;
+
+/// Keeps count of each byte in the last `window` bytes pushed, and how many of those are repeats,
+/// so checking for a marker after every byte is O(1) whatever the window size
+pub struct MarkerDetector(Detector<u8>);
+
+impl MarkerDetector {
+    /// Lowercase letters come first in the alphabet, so puzzle input is counted with a bitmask
+    pub fn new(window: usize) -> Self {
+        Self(Detector::with_alphabet(window, 256, |b: &u8| Some(b.wrapping_sub(b'a') as usize)))
+    }
+
+    /// Whether the fast bitmask path is being used
+    pub fn is_bitmask(&self) -> bool {
+        self.0.is_bitmask()
+    }
+
+    /// Adds the next byte, returning whether the last `window` bytes are now all different
+    pub fn push(&mut self, b: u8) -> bool {
+        self.0.push(b)
+    }
+
+    /// Whether the last `window` bytes are all different
+    pub fn is_marker(&self) -> bool {
+        self.0.is_marker()
+    }
+
+    /// Number of bytes pushed so far
+    pub fn position(&self) -> usize {
+        self.0.position()
+    }
+}
+
+/// Number of bytes read up to and including the first marker of `window` different bytes
+pub fn find_marker(bytes: &[u8], window: usize) -> Option<usize> {
+    if window == 0 {
+        return Some(0);
+    }
+
+    markers(bytes, window).next()
+}
+
+/// Every position where the last `window` bytes are all different, as the number of bytes read up to there
+pub fn markers(bytes: &[u8], window: usize) -> impl Iterator<Item = usize> + '_ {
+    let mut detector = MarkerDetector::new(window);
+
+    bytes
+        .iter()
+        .enumerate()
+        .filter(move |(_, b)| detector.push(**b))
+        .map(|(i, _)| i + 1)
+}
+
+/// Stretch of bytes that are all different
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run {
+    /// Index of the first byte
+    pub start: usize,
+    pub len: usize,
+}
+
+/// The longest stretch of all different bytes, the earliest one if there's a tie. Shows how close a stream
+/// came to having a marker of a given size
+pub fn longest_distinct_run(bytes: &[u8]) -> Option<Run> {
+    let mut last_seen = [None; 256];
+    let mut start = 0;
+    let mut longest: Option<Run> = None;
+
+    for (i, b) in bytes.iter().enumerate() {
+        // The run can't include the previous copy of this byte
+        if let Some(prev) = last_seen[*b as usize] {
+            start = start.max(prev + 1);
+        }
+        last_seen[*b as usize] = Some(i);
+
+        let len = i + 1 - start;
+        if longest.is_none_or(|run| len > run.len) {
+            longest = Some(Run { start, len });
+        }
+    }
+
+    longest
+}
+
+/// Like `find_marker`, reading from `reader` until the marker is found
+pub fn read_marker(reader: impl Read, window: usize) -> io::Result<Option<usize>> {
+    let mut detector = MarkerDetector::new(window);
+    if window == 0 {
+        return Ok(Some(0));
+    }
+
+    for b in BufReader::new(reader).bytes() {
+        if detector.push(b?) {
+            return Ok(Some(detector.position()));
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod day6_detector_tests {
+    use super::*;
+
+    #[test]
+    fn no_marker() {
+        assert_eq!(find_marker(b"aaaaaaa", 2), None);
+        assert_eq!(find_marker(b"abc", 4), None);
+        assert_eq!(read_marker(&b"abcabc"[..], 4).unwrap(), None);
+    }
+
+    #[test]
+    fn marker_at_end() {
+        assert_eq!(find_marker(b"aaabcd", 4), Some(6));
+        assert_eq!(read_marker(&b"aaabcd"[..], 4).unwrap(), Some(6));
+    }
+
+    #[test]
+    fn every_marker() {
+        let all = markers(b"aabcabcc", 3).collect::<Vec<_>>();
+        assert_eq!(all, vec![4, 5, 6, 7]);
+        assert_eq!(markers(b"aaaa", 2).count(), 0);
+    }
+
+    #[test]
+    fn longest_run() {
+        assert_eq!(longest_distinct_run(b""), None);
+        assert_eq!(longest_distinct_run(b"aaaa"), Some(Run { start: 0, len: 1 }));
+        assert_eq!(longest_distinct_run(b"abcabcdab"), Some(Run { start: 3, len: 4 }));
+        assert_eq!(longest_distinct_run(b"abba"), Some(Run { start: 0, len: 2 }));
+
+        // A marker of the longest run's length is found where the run ends, and no longer marker exists
+        let bytes = b"mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        let run = longest_distinct_run(bytes).unwrap();
+        assert_eq!(find_marker(bytes, run.len), Some(run.start + run.len));
+        assert_eq!(find_marker(bytes, run.len + 1), None);
+    }
+
+    #[test]
+    fn large_windows() {
+        let bytes = (0..=255).chain(0..=255).collect::<Vec<u8>>();
+        assert_eq!(find_marker(&bytes, 64), Some(64));
+        assert_eq!(find_marker(&bytes, 256), Some(256));
+        assert_eq!(find_marker(&bytes, 257), None);
+
+        let mut repeated = b"ab".repeat(100);
+        repeated.extend(100..200);
+        // The last "ab" plus 98 new bytes
+        assert_eq!(find_marker(&repeated, 100), Some(298));
+        assert_eq!(find_marker(&repeated, 102), Some(300));
+        assert_eq!(find_marker(&repeated, 103), None);
+    }
+
+    #[test]
+    fn bitmask_for_lowercase() {
+        let mut detector = MarkerDetector::new(14);
+        for b in b"mjqjpqmgbljsphdztnvjfqwrcgsmlb" {
+            detector.push(*b);
+        }
+        assert!(detector.is_bitmask());
+        assert!(detector.is_marker());
+
+        assert!(detector.push(b'A'));
+        assert!(!detector.is_bitmask());
+        assert!(!detector.push(b'l'));
+    }
+}
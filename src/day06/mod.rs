@@ -0,0 +1,43 @@
+pub mod decoder;
+
+pub mod detector;
+use detector::find_marker;
+
+pub mod symbols;
+
+#[aoc(day6, part1)]
+pub fn part1(input: &str) -> usize {
+    find_marker(input.as_bytes(), 4).expect("Didn't find a start sequence")
+}
+
+#[aoc(day6, part2)]
+pub fn part2(input: &str) -> usize {
+    find_marker(input.as_bytes(), 14).expect("Didn't find a start sequence")
+}
+
+#[cfg(test)]
+mod day6_tests {
+    use super::*;
+
+    const SAMPLE_INPUTS: [(&str, usize, usize); 5] = [
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+
+    #[test]
+    fn part1_sample_inputs() {
+        for (input, expected, _) in SAMPLE_INPUTS {
+            assert_eq!(part1(input), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn part2_sample_inputs() {
+        for (input, _, expected) in SAMPLE_INPUTS {
+            assert_eq!(part2(input), expected, "{input}");
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Position of an ASCII letter in `a..=z` then `A..=Z`, an alphabet of 52 symbols
+pub fn letter_index(ch: &char) -> Option<usize> {
+    match ch {
+        'a'..='z' => Some(*ch as usize - 'a' as usize),
+        'A'..='Z' => Some(*ch as usize - 'A' as usize + 26),
+        _ => None,
+    }
+}
+
+enum Counts<S> {
+    /// Bit `n` is flipped every time the symbol with index `n` enters or leaves the window, so only
+    /// symbols seen an odd number of times are set. Every symbol in the window is different exactly
+    /// when `window` bits are set
+    Mask(u64),
+    /// Count of each symbol by index, once a symbol too far into the alphabet for a mask turns up
+    Indexed {
+        counts: Vec<usize>,
+        duplicates: usize,
+    },
+    Hashed {
+        counts: HashMap<S, usize>,
+        /// Symbols in the window that are a repeat of an earlier symbol in the window
+        duplicates: usize,
+    },
+}
+
+/// Index of a symbol in a known alphabet, `None` for anything outside it
+type Index<S> = Box<dyn Fn(&S) -> Option<usize>>;
+
+/// Keeps count of the last `window` symbols pushed and how many are repeats, so checking for a marker
+/// after every symbol is O(1) whatever the window size. Symbols are counted in a `HashMap` unless
+/// the detector is made with an alphabet, then they're counted by index with a bitmask while every
+/// index fits in one, then with an array, until the first symbol outside the alphabet
+pub struct Detector<S: Hash + Eq + Clone> {
+    window: usize,
+    recent: VecDeque<S>,
+    counts: Counts<S>,
+    index: Option<Index<S>>,
+    /// Number of symbols in the alphabet
+    size: usize,
+    position: usize,
+}
+
+impl<S: Hash + Eq + Clone> Detector<S> {
+    pub fn new(window: usize) -> Self {
+        Self {
+            window,
+            recent: VecDeque::with_capacity(window + 1),
+            counts: Counts::Hashed { counts: HashMap::new(), duplicates: 0 },
+            index: None,
+            size: 0,
+            position: 0,
+        }
+    }
+
+    /// Detector for symbols that mostly come from an alphabet of `size` symbols, where `index` gives
+    /// each one's position in it. Putting the most common symbols first keeps it on the bitmask
+    pub fn with_alphabet(window: usize, size: usize, index: impl Fn(&S) -> Option<usize> + 'static) -> Self {
+        Self { counts: Counts::Mask(0), index: Some(Box::new(index)), size, ..Self::new(window) }
+    }
+
+    /// Whether the fast bitmask path is being used
+    pub fn is_bitmask(&self) -> bool {
+        matches!(self.counts, Counts::Mask(_))
+    }
+
+    /// Adds the next symbol, returning whether the last `window` symbols are now all different
+    pub fn push(&mut self, symbol: S) -> bool {
+        self.position += 1;
+        if self.window == 0 {
+            return true;
+        }
+
+        match (&self.counts, self.index_of(&symbol)) {
+            (Counts::Mask(_) | Counts::Indexed { .. }, None) => self.switch_to_hashed(),
+            (Counts::Mask(_), Some(index)) if index >= 64 => self.switch_to_indexed(),
+            _ => {},
+        }
+
+        self.add(&symbol);
+        self.recent.push_back(symbol);
+
+        if self.recent.len() > self.window {
+            let old = self.recent.pop_front().unwrap();
+            self.remove(&old);
+        }
+
+        self.is_marker()
+    }
+
+    /// Whether the last `window` symbols are all different
+    pub fn is_marker(&self) -> bool {
+        self.recent.len() == self.window && match &self.counts {
+            Counts::Mask(mask) => mask.count_ones() as usize == self.window,
+            Counts::Indexed { duplicates, .. } | Counts::Hashed { duplicates, .. } => *duplicates == 0,
+        }
+    }
+
+    /// Number of symbols pushed so far
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Index of `symbol` if it's in the alphabet
+    fn index_of(&self, symbol: &S) -> Option<usize> {
+        self.index.as_ref()?(symbol).filter(|index| *index < self.size)
+    }
+
+    fn switch_to_indexed(&mut self) {
+        self.counts = Counts::Indexed { counts: vec![0; self.size], duplicates: 0 };
+        for symbol in self.recent.clone() {
+            self.add(&symbol);
+        }
+    }
+
+    fn switch_to_hashed(&mut self) {
+        self.counts = Counts::Hashed { counts: HashMap::new(), duplicates: 0 };
+        for symbol in self.recent.clone() {
+            self.add(&symbol);
+        }
+    }
+
+    fn add(&mut self, symbol: &S) {
+        let index = self.index_of(symbol);
+        match &mut self.counts {
+            Counts::Mask(mask) => *mask ^= 1 << index.unwrap(),
+            Counts::Indexed { counts, duplicates } => {
+                let count = &mut counts[index.unwrap()];
+                *count += 1;
+                if *count > 1 {
+                    *duplicates += 1;
+                }
+            },
+            Counts::Hashed { counts, duplicates } => {
+                let count = counts.entry(symbol.clone()).or_insert(0);
+                *count += 1;
+                if *count > 1 {
+                    *duplicates += 1;
+                }
+            },
+        }
+    }
+
+    fn remove(&mut self, symbol: &S) {
+        let index = self.index_of(symbol);
+        match &mut self.counts {
+            Counts::Mask(mask) => *mask ^= 1 << index.unwrap(),
+            Counts::Indexed { counts, duplicates } => {
+                let count = &mut counts[index.unwrap()];
+                if *count > 1 {
+                    *duplicates -= 1;
+                }
+                *count -= 1;
+            },
+            Counts::Hashed { counts, duplicates } => {
+                let count = counts.get_mut(symbol).unwrap();
+                if *count > 1 {
+                    *duplicates -= 1;
+                }
+                *count -= 1;
+                if *count == 0 {
+                    counts.remove(symbol);
+                }
+            },
+        }
+    }
+}
+
+/// Every position where the last `window` symbols are all different, as the number of symbols read up to there
+pub fn markers_in<S: Hash + Eq + Clone>(symbols: impl IntoIterator<Item = S>, window: usize) -> impl Iterator<Item = usize> {
+    markers_with(symbols, Detector::new(window))
+}
+
+/// Like `markers_in`, with a detector that's already been set up, e.g. with an alphabet
+pub fn markers_with<S: Hash + Eq + Clone>(symbols: impl IntoIterator<Item = S>, mut detector: Detector<S>) -> impl Iterator<Item = usize> {
+    symbols
+        .into_iter()
+        .enumerate()
+        .filter(move |(_, s)| detector.push(s.clone()))
+        .map(|(i, _)| i + 1)
+}
+
+/// Number of symbols read up to and including the first marker of `window` different symbols
+pub fn find_marker_in<S: Hash + Eq + Clone>(symbols: impl IntoIterator<Item = S>, window: usize) -> Option<usize> {
+    if window == 0 {
+        return Some(0);
+    }
+
+    markers_in(symbols, window).next()
+}
+
+#[cfg(test)]
+mod day6_symbols_tests {
+    use super::*;
+    use crate::day06::detector::find_marker;
+
+    #[test]
+    fn matches_byte_detector() {
+        for input in ["mjqjpqmgbljsphdztnvjfqwrcgsmlb", "bvwbjplbgvbhsrlpgdmjqwftvncz", "aAbBcCaA"] {
+            for window in [1, 2, 4, 14] {
+                assert_eq!(find_marker_in(input.chars(), window), find_marker(input.as_bytes(), window), "{input} {window}");
+                assert_eq!(find_marker_in(input.bytes(), window), find_marker(input.as_bytes(), window), "{input} {window}");
+
+                let letters = Detector::with_alphabet(window, 52, letter_index);
+                assert_eq!(markers_with(input.chars(), letters).next(), find_marker(input.as_bytes(), window), "{input} {window}");
+            }
+        }
+    }
+
+    #[test]
+    fn unicode() {
+        // Positions are in chars, not bytes
+        assert_eq!(find_marker_in("ééàüöé".chars(), 4), Some(5));
+        assert_eq!(find_marker_in("aaébcd".chars(), 4), Some(5));
+    }
+
+    #[test]
+    fn switches_to_hashing() {
+        let mut detector = Detector::with_alphabet(3, 52, letter_index);
+        for ch in "abc".chars() {
+            detector.push(ch);
+        }
+        assert!(detector.is_bitmask());
+        assert!(detector.is_marker());
+
+        assert!(!detector.push('b'));
+        assert!(detector.push('☃'));
+        assert!(!detector.is_bitmask());
+        assert!(!detector.push('☃'));
+    }
+
+    #[test]
+    fn tokens() {
+        let tokens = "GET POST GET PUT DELETE GET".split(' ');
+        assert_eq!(find_marker_in(tokens.clone(), 3), Some(4));
+        assert_eq!(markers_in(tokens, 3).collect::<Vec<_>>(), vec![4, 5, 6]);
+
+        let numbers = [1_u64, 2, 1, 3, 4, 5];
+        assert_eq!(find_marker_in(numbers, 4), Some(5));
+        assert_eq!(find_marker_in([1_i32, 2, 3], 2), Some(2));
+        assert_eq!(find_marker_in([(1, 'a'), (1, 'a'), (2, 'a')], 2), Some(3));
+    }
+
+    #[test]
+    fn large_windows() {
+        let numbers = (0..200_u32).chain(0..200);
+        assert_eq!(find_marker_in(numbers.clone(), 100), Some(100));
+        assert_eq!(find_marker_in(numbers.clone(), 201), None);
+
+        // Counted with a mask until a symbol too far in for one turns up, then by index
+        let index = |n: &u32| Some(*n as usize);
+        let mut indexed = Detector::with_alphabet(100, 200, index);
+        for n in 0..64 {
+            indexed.push(n);
+        }
+        assert!(indexed.is_bitmask());
+        assert!(!indexed.push(64));
+        assert!(matches!(indexed.counts, Counts::Indexed { .. }));
+        assert_eq!(markers_with(numbers, Detector::with_alphabet(100, 200, index)).next(), Some(100));
+    }
+}
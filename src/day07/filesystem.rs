@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fmt::Display;
 use itertools::Itertools;
 
@@ -16,6 +17,25 @@ impl Node {
             Node::File(file) => file.size(),
         }
     }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Node::Directory(dir) => &dir.name,
+            Node::File(file) => &file.name,
+        }
+    }
+
+    pub fn is_directory(&self) -> bool {
+        matches!(self, Node::Directory(_))
+    }
+
+    /// Index of the directory this is in, `None` for the root and anything that's been removed
+    pub fn parent(&self) -> Option<usize> {
+        match self {
+            Node::Directory(dir) => dir.parent,
+            Node::File(file) => file.parent,
+        }
+    }
 }
 
 impl Display for Node {
@@ -32,11 +52,17 @@ pub struct Directory {
     name: String,
     parent: Option<usize>,
     children: Vec<usize>,
+    /// Index of each child by name, so listings can be merged without searching `children`
+    by_name: HashMap<String, usize>,
     total_size: u64,
     index: usize,
 }
 
 impl Directory {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     pub fn total_size(&self) -> u64 {
         self.total_size
     }
@@ -54,10 +80,15 @@ impl Display for Directory {
 
 pub struct File {
     name: String,
+    parent: Option<usize>,
     size: u64,
 }
 
 impl File {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     pub fn size(&self) -> u64 {
         self.size
     }
@@ -69,6 +100,76 @@ impl Display for File {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictReason {
+    /// File was listed before with a different size
+    SizeMismatch { size: u64, listed_size: u64 },
+    /// Name was listed before as a directory and now as a file, or the other way round
+    KindMismatch { is_directory: bool },
+}
+
+/// Listing line that disagrees with what was listed before. The earlier entry is kept
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    pub line_number: usize,
+    pub name: String,
+    pub reason: ConflictReason,
+}
+
+impl Display for Conflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.reason {
+            ConflictReason::SizeMismatch { size, listed_size } => {
+                write!(f, "line {}: file '{}' listed with size {listed_size} but was {size}", self.line_number, self.name)
+            },
+            ConflictReason::KindMismatch { is_directory: true } => {
+                write!(f, "line {}: '{}' listed as a file but is a directory", self.line_number, self.name)
+            },
+            ConflictReason::KindMismatch { is_directory: false } => {
+                write!(f, "line {}: '{}' listed as a directory but is a file", self.line_number, self.name)
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathError {
+    NotFound(String),
+    NotADirectory(String),
+    AlreadyExists(String),
+    /// Directory moved into itself or something inside it
+    IntoItself(String),
+    /// The root can't be removed or moved
+    Root,
+    /// The current directory, or one it's in, can't be removed
+    InUse(String),
+}
+
+impl Display for PathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathError::NotFound(path) => write!(f, "no such file or directory '{path}'"),
+            PathError::NotADirectory(path) => write!(f, "'{path}' is not a directory"),
+            PathError::AlreadyExists(path) => write!(f, "'{path}' already exists"),
+            PathError::IntoItself(path) => write!(f, "can't move '{path}' into itself"),
+            PathError::Root => write!(f, "can't remove or move the root"),
+            PathError::InUse(path) => write!(f, "can't remove '{path}' while in it"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptError {
+    pub line_number: usize,
+    pub error: PathError,
+}
+
+impl Display for TranscriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line_number, self.error)
+    }
+}
+
 pub struct Filesystem {
     nodes: Vec<Node>,
     curr_index: usize,
@@ -80,6 +181,7 @@ impl Filesystem {
             name: "/".to_string(),
             parent: None,
             children: vec![],
+            by_name: HashMap::new(),
             total_size: 0,
             index: 0,
         };
@@ -91,17 +193,47 @@ impl Filesystem {
         }
     }
 
-    pub fn exec_lines(&mut self, lines: Vec<ParsedLine>) {
-        lines
-        .into_iter()
-        .for_each(|l| {
-            match l {
-                ParsedLine::ChangeDirectory(cd) => self.cd(&cd.dir_name),
-                ParsedLine::Directory(dir) => { self.add_directory(dir.name); },
-                ParsedLine::File(file) => { self.add_file(file.name, file.size); },
-                ParsedLine::List(..) => {},
+    /// Runs a transcript. Directories can be listed any number of times, anything already listed is
+    /// merged by name and any listing that disagrees with an earlier one is returned. Stops at the
+    /// first command that fails, like a `cd` into a directory that hasn't been listed
+    pub fn exec_lines(&mut self, lines: Vec<ParsedLine>) -> Result<Vec<Conflict>, TranscriptError> {
+        let mut conflicts = vec![];
+
+        for (i, l) in lines.into_iter().enumerate() {
+            let error_at = |error| TranscriptError { line_number: i + 1, error };
+
+            let (name, result) = match l {
+                ParsedLine::ChangeDirectory(cd) => {
+                    self.cd(&cd.dir_name).map_err(error_at)?;
+                    continue;
+                },
+                ParsedLine::MakeDirectory(mkdir) => {
+                    self.mkdir(&mkdir.path).map_err(error_at)?;
+                    continue;
+                },
+                ParsedLine::Touch(touch) => {
+                    self.touch(&touch.path, touch.size).map_err(error_at)?;
+                    continue;
+                },
+                ParsedLine::Remove(rm) => {
+                    self.rm(&rm.path).map_err(error_at)?;
+                    continue;
+                },
+                ParsedLine::Move(mv) => {
+                    self.mv(&mv.from, &mv.to).map_err(error_at)?;
+                    continue;
+                },
+                ParsedLine::Directory(dir) => (dir.name.clone(), self.add_directory(dir.name)),
+                ParsedLine::File(file) => (file.name.clone(), self.add_file(file.name, file.size)),
+                ParsedLine::List(..) => continue,
+            };
+
+            if let Err(reason) = result {
+                conflicts.push(Conflict { line_number: i + 1, name, reason });
             }
-        });
+        }
+
+        Ok(conflicts)
     }
 
     pub fn root_dir(&self) -> &Directory {
@@ -125,6 +257,7 @@ impl Filesystem {
         }
     }
 
+    /// Node at `index`. Removed nodes are still here, but have no parent so `path_of` gives `None`
     pub fn get_node(&self, index: usize) -> Option<&Node> {
         self.nodes.get(index)
     }
@@ -133,40 +266,90 @@ impl Filesystem {
         self.nodes.get_mut(index)
     }
 
+    /// Every directory that can be reached from the root, so not ones that have been removed
     pub fn get_directories(&self) -> Vec<&Directory> {
-        self.nodes
-            .iter()
-            .filter_map(|node| match node {
+        self.walk()
+            .filter_map(|(_, node)| match node {
                 Node::Directory(dir) => Some(dir),
                 Node::File(_) => None,
             })
             .collect()
     }
 
-    pub fn cd(&mut self, dir_name: &String) {
-        if dir_name == "/" {
-            return self.cd_index(0);
+    /// Changes directory to an absolute path, or one relative to the current directory. Paths can
+    /// have any number of segments, including `.` and `..`
+    pub fn cd(&mut self, path: &str) -> Result<(), PathError> {
+        let index = self.resolve(path)?;
+        self.cd_index(index);
+        Ok(())
+    }
+
+    /// Index of the file or directory at `path`, which is absolute or relative to the current directory
+    pub fn lookup(&self, path: &str) -> Result<usize, PathError> {
+        let mut index = if path.starts_with('/') { 0 } else { self.curr_dir().index };
+
+        for segment in path.split('/').filter(|s| !s.is_empty()) {
+            let dir = match &self.nodes[index] {
+                Node::Directory(dir) => dir,
+                Node::File(_) => return Err(PathError::NotADirectory(path.to_string())),
+            };
+
+            index = match segment {
+                "." => index,
+                // Going up from the root stays at the root, like a real shell
+                ".." => dir.parent.unwrap_or(0),
+                name => self.find_child(index, name).ok_or_else(|| PathError::NotFound(path.to_string()))?,
+            };
+        }
+
+        Ok(index)
+    }
+
+    /// Index of the directory at `path`
+    fn resolve(&self, path: &str) -> Result<usize, PathError> {
+        let index = self.lookup(path)?;
+        match &self.nodes[index] {
+            Node::Directory(dir) => Ok(dir.index),
+            Node::File(_) => Err(PathError::NotADirectory(path.to_string())),
         }
+    }
 
-        if dir_name == ".." {
-            let index = self.curr_dir().parent.unwrap_or(0);
-            return self.cd_index(index);
+    /// Absolute path of the node at `index`, `None` if it's been removed so can't be reached from the root
+    pub fn path_of(&self, index: usize) -> Option<String> {
+        let mut names = vec![];
+        let mut index = index;
+        let mut node = self.nodes.get(index)?;
+        while let Some(parent) = node.parent() {
+            names.push(node.name());
+            index = parent;
+            node = &self.nodes[parent];
         }
 
-        let index = self.curr_dir()
-            .children
-            .iter()
-            .filter_map(|i| {
-                match self.get_node(*i).unwrap() {
-                    Node::Directory(dir) => Some(dir),
-                    Node::File(_) => None,
-                }
-            })
-            .find(|dir| dir.name.eq(dir_name))
-            .unwrap()
-            .index;
+        (index == 0).then(|| format!("/{}", names.iter().rev().join("/")))
+    }
 
-        self.cd_index(index)
+    /// Every file and directory with its absolute path, depth first from the root, in the order they were listed
+    pub fn walk(&self) -> Walk<'_> {
+        Walk { fs: self, stack: vec![("/".to_string(), 0)] }
+    }
+
+    /// Every file and directory whose path and node match `predicate`, in the same order as `walk`
+    pub fn find<'a>(&'a self, mut predicate: impl FnMut(&str, &Node) -> bool + 'a) -> impl Iterator<Item = (String, &'a Node)> {
+        self.walk().filter(move |(path, node)| predicate(path, node))
+    }
+
+    fn dir(&self, index: usize) -> &Directory {
+        match &self.nodes[index] {
+            Node::Directory(dir) => dir,
+            _ => panic!("Node {index} isn't a directory"),
+        }
+    }
+
+    fn dir_mut(&mut self, index: usize) -> &mut Directory {
+        match &mut self.nodes[index] {
+            Node::Directory(dir) => dir,
+            _ => panic!("Node {index} isn't a directory"),
+        }
     }
 
     fn cd_index(&mut self, index: usize) {
@@ -179,51 +362,228 @@ impl Filesystem {
         }
     }
 
-    pub fn add_directory(&mut self, name: String) -> usize {       
-        let parent_index = self.curr_index;
+    fn find_child(&self, dir_index: usize, name: &str) -> Option<usize> {
+        self.dir(dir_index).by_name.get(name).copied()
+    }
+
+    fn add_child(&mut self, dir_index: usize, name: String, child_index: usize) {
+        let dir = self.dir_mut(dir_index);
+        dir.children.push(child_index);
+        dir.by_name.insert(name, child_index);
+    }
+
+    /// Adds a directory to the current directory, or gets the existing one with the same name
+    pub fn add_directory(&mut self, name: String) -> Result<usize, ConflictReason> {
+        self.add_directory_in(self.curr_index, name)
+    }
+
+    fn add_directory_in(&mut self, parent_index: usize, name: String) -> Result<usize, ConflictReason> {
+        if let Some(existing) = self.find_child(parent_index, &name) {
+            return match &self.nodes[existing] {
+                Node::Directory(_) => Ok(existing),
+                Node::File(_) => Err(ConflictReason::KindMismatch { is_directory: false }),
+            };
+        }
+
         let child_index = self.nodes.len();
 
         let dir = Directory {
-            name,
+            name: name.clone(),
             parent: Some(parent_index),
             children: vec![],
+            by_name: HashMap::new(),
             total_size: 0,
             index: child_index,
         };
         let node = Node::Directory(dir);
         self.nodes.push(node);
 
-        self.curr_dir_mut().children.push(child_index);
+        self.add_child(parent_index, name, child_index);
 
-        child_index
+        Ok(child_index)
     }
 
-    pub fn add_file(&mut self, name: String, size: u64) -> usize {
+    /// Adds a file to the current directory, or gets the existing one if it's the same size
+    pub fn add_file(&mut self, name: String, size: u64) -> Result<usize, ConflictReason> {
+        self.add_file_in(self.curr_index, name, size)
+    }
+
+    fn add_file_in(&mut self, parent_index: usize, name: String, size: u64) -> Result<usize, ConflictReason> {
+        if let Some(existing) = self.find_child(parent_index, &name) {
+            return match &self.nodes[existing] {
+                Node::File(file) if file.size == size => Ok(existing),
+                Node::File(file) => Err(ConflictReason::SizeMismatch { size: file.size, listed_size: size }),
+                Node::Directory(_) => Err(ConflictReason::KindMismatch { is_directory: true }),
+            };
+        }
+
         let child_index = self.nodes.len();
 
         let file = File {
-            name,
+            name: name.clone(),
+            parent: Some(parent_index),
             size,
         };
         let node = Node::File(file);
         self.nodes.push(node);
 
         // Add index to children
-        self.curr_dir_mut().children.push(child_index);
+        self.add_child(parent_index, name, child_index);
 
         // Update dir sizes
-        let mut index = Some(self.curr_index);
+        self.update_total_sizes(parent_index, size, 0);
+
+        Ok(child_index)
+    }
+
+    /// Adds `added` to and takes `removed` from the total size of the directory at `index` and every
+    /// directory above it
+    fn update_total_sizes(&mut self, index: usize, added: u64, removed: u64) {
+        let mut index = Some(index);
+        while let Some(i) = index {
+            let dir = self.dir_mut(i);
+            dir.total_size = dir.total_size + added - removed;
+
+            index = dir.parent;
+        }
+    }
+
+    /// Whether the node at `index` is `ancestor` or somewhere inside it
+    fn is_within(&self, index: usize, ancestor: usize) -> bool {
+        let mut index = Some(index);
         while let Some(i) = index {
-            if let Some(Node::Directory(dir)) = self.get_node_mut(i) {
-                // TODO if this is over 100,000 we don't need to add
-                // TODO if this addition goes over 100,000 we can stop iterating
-                dir.total_size += size;
-                
-                index = dir.parent;
+            if i == ancestor {
+                return true;
             }
+            index = self.nodes[i].parent();
+        }
+
+        false
+    }
+
+    /// Directory a new entry at `path` would go in, and its name. Fails if there's already something there
+    fn new_entry<'p>(&self, path: &'p str) -> Result<(usize, &'p str), PathError> {
+        if self.lookup(path).is_ok() {
+            return Err(PathError::AlreadyExists(path.to_string()));
+        }
+
+        let (parent_path, name) = match path.trim_end_matches('/').rsplit_once('/') {
+            Some(("", name)) => ("/", name),
+            Some((parent_path, name)) => (parent_path, name),
+            None => (".", path.trim_end_matches('/')),
+        };
+
+        Ok((self.resolve(parent_path)?, name))
+    }
+
+    /// Takes the node at `index` out of its directory. It stays in `nodes` but has no parent, so it
+    /// and everything in it can't be reached any more
+    fn detach(&mut self, index: usize) {
+        let node = &self.nodes[index];
+        let (parent, size, name) = (node.parent().unwrap(), node.size(), node.name().to_string());
+
+        let dir = self.dir_mut(parent);
+        dir.children.retain(|i| *i != index);
+        dir.by_name.remove(&name);
+        self.update_total_sizes(parent, 0, size);
+
+        match &mut self.nodes[index] {
+            Node::Directory(dir) => dir.parent = None,
+            Node::File(file) => file.parent = None,
+        }
+    }
+
+    /// Puts the detached node at `index` in the directory at `parent`, as `name`
+    fn attach(&mut self, index: usize, parent: usize, name: String) {
+        let size = match &mut self.nodes[index] {
+            Node::Directory(dir) => {
+                dir.name = name.clone();
+                dir.parent = Some(parent);
+                dir.total_size
+            },
+            Node::File(file) => {
+                file.name = name.clone();
+                file.parent = Some(parent);
+                file.size
+            },
+        };
+
+        self.add_child(parent, name, index);
+        self.update_total_sizes(parent, size, 0);
+    }
+
+    /// Makes a directory at `path`, in a directory that already exists
+    pub fn mkdir(&mut self, path: &str) -> Result<usize, PathError> {
+        let (parent, name) = self.new_entry(path)?;
+        Ok(self.add_directory_in(parent, name.to_string()).unwrap())
+    }
+
+    /// Makes a file at `path` with `size`, or changes the size of the file already there
+    pub fn touch(&mut self, path: &str, size: u64) -> Result<usize, PathError> {
+        if let Ok(index) = self.lookup(path) {
+            return match &mut self.nodes[index] {
+                Node::File(file) => {
+                    let (parent, old_size) = (file.parent.unwrap(), file.size);
+                    file.size = size;
+                    self.update_total_sizes(parent, size, old_size);
+                    Ok(index)
+                },
+                Node::Directory(_) => Err(PathError::AlreadyExists(path.to_string())),
+            };
         }
 
-        child_index
+        let (parent, name) = self.new_entry(path)?;
+        Ok(self.add_file_in(parent, name.to_string(), size).unwrap())
+    }
+
+    /// Removes the file or directory at `path`, and everything in it. The current directory, or any
+    /// directory it's in, can't be removed
+    pub fn rm(&mut self, path: &str) -> Result<(), PathError> {
+        let index = self.lookup(path)?;
+        if index == 0 {
+            return Err(PathError::Root);
+        }
+        if self.is_within(self.curr_index, index) {
+            return Err(PathError::InUse(path.to_string()));
+        }
+
+        self.detach(index);
+
+        Ok(())
+    }
+
+    /// Moves the file or directory at `from` into the directory at `to`, or renames it to `to` if
+    /// nothing is there. A file can replace another file, but nothing else can be replaced
+    pub fn mv(&mut self, from: &str, to: &str) -> Result<usize, PathError> {
+        let index = self.lookup(from)?;
+        if index == 0 {
+            return Err(PathError::Root);
+        }
+
+        let (parent, name) = match self.lookup(to) {
+            Ok(target) if self.nodes[target].is_directory() => (target, self.nodes[index].name().to_string()),
+            Ok(target) => (self.nodes[target].parent().unwrap(), self.nodes[target].name().to_string()),
+            Err(_) => {
+                let (parent, name) = self.new_entry(to)?;
+                (parent, name.to_string())
+            },
+        };
+
+        if self.is_within(parent, index) {
+            return Err(PathError::IntoItself(from.to_string()));
+        }
+
+        match self.find_child(parent, &name) {
+            Some(existing) if existing == index => return Ok(index),
+            Some(existing) if !self.nodes[existing].is_directory() && !self.nodes[index].is_directory() => self.detach(existing),
+            Some(_) => return Err(PathError::AlreadyExists(to.to_string())),
+            None => {},
+        }
+
+        self.detach(index);
+        self.attach(index, parent, name);
+
+        Ok(index)
     }
 
     #[allow(clippy::only_used_in_recursion)]
@@ -253,6 +613,36 @@ impl Filesystem {
     }
 }
 
+impl Default for Filesystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct Walk<'a> {
+    fs: &'a Filesystem,
+    /// Paths and indexes still to visit, next one last
+    stack: Vec<(String, usize)>,
+}
+
+impl<'a> Iterator for Walk<'a> {
+    type Item = (String, &'a Node);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, index) = self.stack.pop()?;
+        let node = &self.fs.nodes[index];
+
+        if let Node::Directory(dir) = node {
+            for child in dir.children.iter().rev() {
+                let child_path = if path == "/" { path.clone() } else { format!("{path}/") } + self.fs.nodes[*child].name();
+                self.stack.push((child_path, *child));
+            }
+        }
+
+        Some((path, node))
+    }
+}
+
 impl Display for Filesystem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = self.dir_to_string(self.root_dir(), 0);
@@ -264,18 +654,20 @@ impl Display for Filesystem {
 #[cfg(test)]
 mod day7_filesystem_tests {
     use super::*;
+    use crate::day07::day7_tests::SAMPLE_INPUT;
+    use crate::day07::lines::parse;
 
     #[test]
     fn directories_with_0_size_should_have_no_children() {
         let mut fs = Filesystem::new();
 
-        fs.cd(&"/".to_string());
-        fs.add_directory("a1".to_string());
-        fs.add_directory("a2".to_string());
-        fs.cd(&"a2".to_string());
-        fs.add_file("file".to_string(), 1234);
-        fs.add_directory("a3".to_string());
-        fs.cd(&"a3".to_string());
+        fs.cd("/").unwrap();
+        fs.add_directory("a1".to_string()).unwrap();
+        fs.add_directory("a2".to_string()).unwrap();
+        fs.cd("a2").unwrap();
+        fs.add_file("file".to_string(), 1234).unwrap();
+        fs.add_directory("a3".to_string()).unwrap();
+        fs.cd("a3").unwrap();
 
         let dirs = fs.get_directories();
         assert_eq!(dirs.len(), 4);
@@ -291,4 +683,188 @@ mod day7_filesystem_tests {
                 }
             })
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn repeated_listings_are_merged() {
+        let mut fs = Filesystem::new();
+
+        for _ in 0..2 {
+            fs.cd("/").unwrap();
+            fs.add_directory("a".to_string()).unwrap();
+            fs.add_file("b".to_string(), 10).unwrap();
+            fs.cd("a").unwrap();
+            fs.add_file("c".to_string(), 5).unwrap();
+        }
+
+        assert_eq!(fs.get_directories().len(), 2);
+        assert_eq!(fs.root_dir().total_size(), 15);
+        assert_eq!(fs.curr_dir().total_size(), 5);
+    }
+
+    #[test]
+    fn conflicting_listings() {
+        let mut fs = Filesystem::new();
+        fs.add_directory("a".to_string()).unwrap();
+        fs.add_file("b".to_string(), 10).unwrap();
+
+        assert_eq!(fs.add_file("b".to_string(), 11), Err(ConflictReason::SizeMismatch { size: 10, listed_size: 11 }));
+        assert_eq!(fs.add_file("a".to_string(), 11), Err(ConflictReason::KindMismatch { is_directory: true }));
+        assert_eq!(fs.add_directory("b".to_string()), Err(ConflictReason::KindMismatch { is_directory: false }));
+        assert_eq!(fs.root_dir().total_size(), 10);
+    }
+
+    #[test]
+    fn large_directory() {
+        let mut fs = Filesystem::new();
+        for _ in 0..2 {
+            for i in 0..50_000 {
+                fs.add_file(format!("f{i}"), 1).unwrap();
+            }
+        }
+
+        assert_eq!(fs.root_dir().children().len(), 50_000);
+        assert_eq!(fs.root_dir().total_size(), 50_000);
+        assert_eq!(fs.lookup("/f49999"), Ok(50_000));
+    }
+
+    #[test]
+    fn cd_paths() {
+        let mut fs = Filesystem::new();
+        fs.add_directory("a".to_string()).unwrap();
+        fs.add_file("f".to_string(), 1).unwrap();
+        fs.cd("a").unwrap();
+        fs.add_directory("b".to_string()).unwrap();
+        fs.cd("b").unwrap();
+        fs.add_directory("c".to_string()).unwrap();
+
+        fs.cd("/a/b/c").unwrap();
+        assert_eq!(fs.curr_dir().name, "c");
+        fs.cd("../../b/./c/..").unwrap();
+        assert_eq!(fs.curr_dir().name, "b");
+        fs.cd(".").unwrap();
+        assert_eq!(fs.curr_dir().name, "b");
+        fs.cd("/../../a//b/").unwrap();
+        assert_eq!(fs.curr_dir().name, "b");
+        fs.cd("/").unwrap();
+        assert_eq!(fs.curr_dir().name, "/");
+
+        assert_eq!(fs.cd("a/x"), Err(PathError::NotFound("a/x".to_string())));
+        assert_eq!(fs.cd("/f"), Err(PathError::NotADirectory("/f".to_string())));
+        assert_eq!(fs.curr_dir().name, "/");
+    }
+
+    fn sample() -> Filesystem {
+        let mut fs = Filesystem::new();
+        fs.exec_lines(parse(SAMPLE_INPUT)).unwrap();
+        fs.cd("/").unwrap();
+        fs
+    }
+
+    fn size(fs: &Filesystem, path: &str) -> u64 {
+        fs.get_node(fs.lookup(path).unwrap()).unwrap().size()
+    }
+
+    #[test]
+    fn lookup_and_path_of() {
+        let fs = sample();
+
+        for path in ["/", "/a", "/a/e", "/a/e/i", "/b.txt", "/d/d.log"] {
+            let index = fs.lookup(path).unwrap();
+            assert_eq!(fs.path_of(index).unwrap(), path);
+        }
+
+        let i = fs.get_node(fs.lookup("a/./e/../e/i").unwrap()).unwrap();
+        assert_eq!(i.name(), "i");
+        assert_eq!(i.size(), 584);
+        assert!(!i.is_directory());
+
+        assert_eq!(fs.lookup("/a/x"), Err(PathError::NotFound("/a/x".to_string())));
+        assert_eq!(fs.lookup("/b.txt/c"), Err(PathError::NotADirectory("/b.txt/c".to_string())));
+        assert_eq!(fs.path_of(100), None);
+    }
+
+    #[test]
+    fn walk_and_find() {
+        let fs = sample();
+
+        let paths = fs.walk().map(|(path, _)| path).collect::<Vec<_>>();
+        assert_eq!(paths, vec![
+            "/", "/a", "/a/e", "/a/e/i", "/a/f", "/a/g", "/a/h.lst", "/b.txt", "/c.dat", "/d", "/d/j", "/d/d.log", "/d/d.ext", "/d/k",
+        ]);
+
+        let dirs = fs.find(|_, node| node.is_directory() && node.size() < 100_000).map(|(path, _)| path).collect::<Vec<_>>();
+        assert_eq!(dirs, vec!["/a", "/a/e"]);
+
+        let files = fs.find(|path, _| path.starts_with("/a/")).filter(|(_, node)| !node.is_directory()).count();
+        assert_eq!(files, 4);
+    }
+
+    #[test]
+    fn mkdir_and_touch() {
+        let mut fs = sample();
+
+        fs.mkdir("/a/e/new").unwrap();
+        fs.touch("a/e/new/x", 16).unwrap();
+        assert_eq!(size(&fs, "/a/e"), 600);
+
+        // Touching an existing file changes its size
+        fs.touch("/a/e/new/x", 6).unwrap();
+        assert_eq!(size(&fs, "/a"), 94853 + 6);
+        assert_eq!(fs.root_dir().total_size(), 48381165 + 6);
+
+        assert_eq!(fs.mkdir("/a/e"), Err(PathError::AlreadyExists("/a/e".to_string())));
+        assert_eq!(fs.mkdir("/x/y"), Err(PathError::NotFound("/x".to_string())));
+        assert_eq!(fs.touch("/a", 1), Err(PathError::AlreadyExists("/a".to_string())));
+    }
+
+    #[test]
+    fn rm_detaches() {
+        let mut fs = sample();
+        let e = fs.lookup("/a/e").unwrap();
+        let i = fs.lookup("/a/e/i").unwrap();
+        fs.cd("/a/e").unwrap();
+
+        assert_eq!(fs.rm("/a"), Err(PathError::InUse("/a".to_string())));
+        fs.cd("..").unwrap();
+        fs.rm("/a/e").unwrap();
+
+        assert_eq!(fs.curr_dir().name(), "a");
+        assert_eq!(fs.root_dir().total_size(), 48381165 - 584);
+        assert_eq!(fs.get_directories().len(), 3);
+        assert!(fs.get_node(e).is_some());
+        assert_eq!(fs.path_of(e), None);
+        assert_eq!(fs.path_of(i), None);
+        assert!(fs.lookup("/a/e/i").is_err());
+
+        fs.rm("../b.txt").unwrap();
+        assert!(fs.lookup("/b.txt").is_err());
+        assert_eq!(fs.root_dir().total_size(), 48381165 - 584 - 14848514);
+        assert_eq!(fs.rm("/"), Err(PathError::Root));
+    }
+
+    #[test]
+    fn mv_keeps_sizes() {
+        let mut fs = sample();
+
+        fs.mv("/a/e", "/").unwrap();
+        assert_eq!(size(&fs, "/a"), 94853 - 584);
+        assert_eq!(size(&fs, "/e"), 584);
+        assert_eq!(fs.root_dir().total_size(), 48381165);
+
+        fs.mv("/b.txt", "/e/renamed").unwrap();
+        fs.mv("/a/f", "e/i").unwrap();
+        let e = fs.find(|path, _| path.starts_with("/e")).map(|(path, node)| (path, node.size())).collect::<Vec<_>>();
+        assert_eq!(e, vec![
+            ("/e".to_string(), 29116 + 14848514),
+            ("/e/renamed".to_string(), 14848514),
+            ("/e/i".to_string(), 29116),
+        ]);
+        assert_eq!(size(&fs, "/a"), 2557 + 62596);
+        assert_eq!(fs.root_dir().total_size(), 48381165 - 584);
+
+        assert_eq!(fs.mv("/e", "/e/x"), Err(PathError::IntoItself("/e".to_string())));
+        assert_eq!(fs.mv("/a", "/e/i"), Err(PathError::AlreadyExists("/e/i".to_string())));
+        assert_eq!(fs.mv("/", "/a"), Err(PathError::Root));
+        assert_eq!(fs.mv("/e/i", "/e"), Ok(fs.lookup("/e/i").unwrap()));
+    }
+}
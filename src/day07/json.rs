@@ -0,0 +1,234 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::filesystem::{ConflictReason, Filesystem, Node};
+
+/// A file or directory and everything in it, as it's written in JSON. Directories' total sizes are
+/// always written, and checked if they're there when reading
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Entry {
+    File {
+        name: String,
+        size: u64,
+    },
+    Directory {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        total_size: Option<u64>,
+        #[serde(default)]
+        children: Vec<Entry>,
+    },
+}
+
+impl Entry {
+    /// Everything in `fs`, starting from the root
+    pub fn of(fs: &Filesystem) -> Self {
+        Self::of_node(fs, fs.get_node(0).unwrap())
+    }
+
+    fn of_node(fs: &Filesystem, node: &Node) -> Self {
+        match node {
+            Node::File(file) => Entry::File { name: file.name().to_string(), size: file.size() },
+            Node::Directory(dir) => Entry::Directory {
+                name: dir.name().to_string(),
+                total_size: Some(dir.total_size()),
+                children: dir.children().iter().map(|i| Self::of_node(fs, fs.get_node(*i).unwrap())).collect(),
+            },
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Entry::File { name, .. } | Entry::Directory { name, .. } => name,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+impl FromStr for Entry {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str::<Entry>(s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportError {
+    Json(String),
+    RootIsFile,
+    /// Name that can't be `cd`ed into, like `..` or one with a `/` in it
+    InvalidName(String),
+    Conflict { path: String, reason: ConflictReason },
+    TotalSizeMismatch { path: String, total_size: u64, listed_total_size: u64 },
+}
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportError::Json(e) => write!(f, "invalid JSON: {e}"),
+            ImportError::RootIsFile => write!(f, "the root has to be a directory"),
+            ImportError::InvalidName(name) => write!(f, "invalid name '{name}'"),
+            ImportError::Conflict { path, reason: ConflictReason::SizeMismatch { .. } } => {
+                write!(f, "'{path}' is listed more than once with different sizes")
+            },
+            ImportError::Conflict { path, reason: ConflictReason::KindMismatch { .. } } => {
+                write!(f, "'{path}' is listed as both a file and a directory")
+            },
+            ImportError::TotalSizeMismatch { path, total_size, listed_total_size } => {
+                write!(f, "'{path}' listed with total size {listed_total_size} but is {total_size}")
+            },
+        }
+    }
+}
+
+/// Serializes everything in `fs` as nested JSON
+pub fn to_json(fs: &Filesystem) -> String {
+    Entry::of(fs).to_json()
+}
+
+/// Rebuilds a filesystem from JSON written by `to_json`, or by hand
+pub fn from_json(s: &str) -> Result<Filesystem, ImportError> {
+    let entry = s.parse::<Entry>().map_err(|e| ImportError::Json(e.to_string()))?;
+    from_entry(&entry)
+}
+
+/// Rebuilds a filesystem from `root`, whose name is ignored as the root is always `/`
+pub fn from_entry(root: &Entry) -> Result<Filesystem, ImportError> {
+    let Entry::Directory { children, .. } = root else {
+        return Err(ImportError::RootIsFile);
+    };
+
+    let mut fs = Filesystem::new();
+    add_children(&mut fs, "", children)?;
+    check_total_sizes(&fs, "/", root)?;
+    fs.cd("/").unwrap();
+
+    Ok(fs)
+}
+
+/// Adds `children` to the current directory, which is at `path`
+fn add_children(fs: &mut Filesystem, path: &str, children: &[Entry]) -> Result<(), ImportError> {
+    for child in children {
+        let name = child.name();
+        if name.is_empty() || name == "." || name == ".." || name.contains('/') || name.contains('\n') {
+            return Err(ImportError::InvalidName(name.to_string()));
+        }
+
+        let child_path = format!("{path}/{name}");
+        let added = match child {
+            Entry::File { size, .. } => fs.add_file(name.to_string(), *size),
+            Entry::Directory { .. } => fs.add_directory(name.to_string()),
+        };
+        added.map_err(|reason| ImportError::Conflict { path: child_path.clone(), reason })?;
+
+        if let Entry::Directory { children, .. } = child {
+            fs.cd(name).unwrap();
+            add_children(fs, &child_path, children)?;
+            fs.cd("..").unwrap();
+        }
+    }
+
+    Ok(())
+}
+
+fn check_total_sizes(fs: &Filesystem, path: &str, entry: &Entry) -> Result<(), ImportError> {
+    let Entry::Directory { total_size, children, .. } = entry else {
+        return Ok(());
+    };
+
+    let actual = fs.get_node(fs.lookup(path).unwrap()).unwrap().size();
+    if let Some(listed) = total_size {
+        if *listed != actual {
+            return Err(ImportError::TotalSizeMismatch { path: path.to_string(), total_size: actual, listed_total_size: *listed });
+        }
+    }
+
+    for child in children {
+        let child_path = if path == "/" { format!("/{}", child.name()) } else { format!("{path}/{}", child.name()) };
+        check_total_sizes(fs, &child_path, child)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod day7_json_tests {
+    use super::*;
+    use crate::day07::day7_tests::SAMPLE_INPUT;
+    use crate::day07::lines::parse;
+
+    #[test]
+    fn round_trip() {
+        let mut fs = Filesystem::new();
+        fs.exec_lines(parse(SAMPLE_INPUT)).unwrap();
+
+        let json = to_json(&fs);
+        let loaded = from_json(&json).unwrap();
+
+        assert_eq!(Entry::of(&loaded), Entry::of(&fs));
+        assert_eq!(to_json(&loaded), json);
+        assert_eq!(loaded.walk().map(|(path, node)| (path, node.size())).collect::<Vec<_>>(),
+                   fs.walk().map(|(path, node)| (path, node.size())).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn document_format() {
+        let mut fs = Filesystem::new();
+        fs.add_directory("a".to_string()).unwrap();
+        fs.add_file("b".to_string(), 10).unwrap();
+
+        let value = serde_json::from_str::<serde_json::Value>(&to_json(&fs)).unwrap();
+        assert_eq!(value, serde_json::json!({
+            "type": "directory",
+            "name": "/",
+            "total_size": 10,
+            "children": [
+                { "type": "directory", "name": "a", "total_size": 0, "children": [] },
+                { "type": "file", "name": "b", "size": 10 },
+            ],
+        }));
+    }
+
+    #[test]
+    fn total_sizes_are_optional() {
+        let json = r#"{"type": "directory", "name": "/", "children": [
+            {"type": "directory", "name": "a", "children": [{"type": "file", "name": "x", "size": 3}]},
+            {"type": "file", "name": "y", "size": 4}
+        ]}"#;
+        let fs = from_json(json).unwrap();
+
+        assert_eq!(fs.root_dir().total_size(), 7);
+        assert_eq!(fs.get_node(fs.lookup("/a/x").unwrap()).unwrap().size(), 3);
+    }
+
+    #[test]
+    fn invalid_documents() {
+        let err = |json: &str| from_json(json).err().unwrap();
+
+        assert_eq!(err(r#"{"type": "file", "name": "/", "size": 1}"#), ImportError::RootIsFile);
+        assert!(matches!(err("{"), ImportError::Json(_)));
+        assert_eq!(
+            err(r#"{"type": "directory", "name": "/", "children": [{"type": "directory", "name": "..", "children": []}]}"#),
+            ImportError::InvalidName("..".to_string()),
+        );
+        assert_eq!(
+            err(r#"{"type": "directory", "name": "/", "children": [
+                {"type": "directory", "name": "a", "total_size": 5, "children": [{"type": "file", "name": "x", "size": 3}]}
+            ]}"#),
+            ImportError::TotalSizeMismatch { path: "/a".to_string(), total_size: 3, listed_total_size: 5 },
+        );
+        assert_eq!(
+            err(r#"{"type": "directory", "name": "/", "children": [
+                {"type": "file", "name": "x", "size": 3}, {"type": "file", "name": "x", "size": 4}
+            ]}"#).to_string(),
+            "'/x' is listed more than once with different sizes",
+        );
+    }
+}
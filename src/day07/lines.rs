@@ -6,6 +6,10 @@ lazy_static! {
     static ref LS_REGEX: Regex = Regex::new(r"^\$ ls$").unwrap();
     static ref DIR_REGEX: Regex = Regex::new(r"^dir (.*)$").unwrap();
     static ref FILE_REGEX: Regex = Regex::new(r"^(\d+) (.*)$").unwrap();
+    static ref MKDIR_REGEX: Regex = Regex::new(r"^\$ mkdir (.+)$").unwrap();
+    static ref TOUCH_REGEX: Regex = Regex::new(r"^\$ touch (\d+) (.+)$").unwrap();
+    static ref RM_REGEX: Regex = Regex::new(r"^\$ rm (.+)$").unwrap();
+    static ref MV_REGEX: Regex = Regex::new(r"^\$ mv (\S+) (\S+)$").unwrap();
 }
 
 
@@ -87,12 +91,109 @@ impl TryFrom<&str> for FileLine {
     }
 }
 
+/// `$ mkdir <path>`, makes a directory in one that already exists
+#[derive(Debug)]
+pub struct MakeDirectoryLine {
+    pub path: String,
+}
+
+impl TryFrom<&str> for MakeDirectoryLine {
+    type Error = &'static str;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let path = MKDIR_REGEX
+            .captures(value).ok_or("Parsing MakeDirectoryLine gave no captures")?
+            .get(1).ok_or("Parsed MakeDirectoryLine missing path")?
+            .as_str()
+            .to_string();
+
+        Ok(MakeDirectoryLine { path })
+    }
+}
+
+/// `$ touch <size> <path>`, makes a file or changes the size of an existing one
+#[derive(Debug)]
+pub struct TouchLine {
+    pub path: String,
+    pub size: u64,
+}
+
+impl TryFrom<&str> for TouchLine {
+    type Error = &'static str;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let captures = TOUCH_REGEX
+            .captures(value).ok_or("Parsing TouchLine gave no captures")?;
+        let size = captures
+            .get(1).ok_or("Parsed TouchLine missing size")?
+            .as_str()
+            .parse::<u64>().or(Err("Unable to parse file size from parsed TouchLine"))?;
+        let path = captures
+            .get(2).ok_or("Parsed TouchLine missing path")?
+            .as_str()
+            .to_string();
+
+        Ok(TouchLine { path, size })
+    }
+}
+
+/// `$ rm <path>`, removes a file or a directory and everything in it. Fails for the current directory
+/// or any directory it's in
+#[derive(Debug)]
+pub struct RemoveLine {
+    pub path: String,
+}
+
+impl TryFrom<&str> for RemoveLine {
+    type Error = &'static str;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let path = RM_REGEX
+            .captures(value).ok_or("Parsing RemoveLine gave no captures")?
+            .get(1).ok_or("Parsed RemoveLine missing path")?
+            .as_str()
+            .to_string();
+
+        Ok(RemoveLine { path })
+    }
+}
+
+/// `$ mv <from> <to>`, moves or renames a file or directory. Neither path can have spaces in it
+#[derive(Debug)]
+pub struct MoveLine {
+    pub from: String,
+    pub to: String,
+}
+
+impl TryFrom<&str> for MoveLine {
+    type Error = &'static str;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let captures = MV_REGEX
+            .captures(value).ok_or("Parsing MoveLine gave no captures")?;
+        let from = captures
+            .get(1).ok_or("Parsed MoveLine missing source")?
+            .as_str()
+            .to_string();
+        let to = captures
+            .get(2).ok_or("Parsed MoveLine missing destination")?
+            .as_str()
+            .to_string();
+
+        Ok(MoveLine { from, to })
+    }
+}
+
 #[derive(Debug)]
 pub enum ParsedLine {
     ChangeDirectory(ChangeDirectoryLine),
     List(ListLine),
     Directory(DirectoryLine),
     File(FileLine),
+    MakeDirectory(MakeDirectoryLine),
+    Touch(TouchLine),
+    Remove(RemoveLine),
+    Move(MoveLine),
 }
 
 impl TryFrom<&str> for ParsedLine {
@@ -103,6 +204,14 @@ impl TryFrom<&str> for ParsedLine {
             Ok(ParsedLine::ChangeDirectory(parsed))
         } else if let Ok(parsed) = ListLine::try_from(value) {
             Ok(ParsedLine::List(parsed))
+        } else if let Ok(parsed) = MakeDirectoryLine::try_from(value) {
+            Ok(ParsedLine::MakeDirectory(parsed))
+        } else if let Ok(parsed) = TouchLine::try_from(value) {
+            Ok(ParsedLine::Touch(parsed))
+        } else if let Ok(parsed) = RemoveLine::try_from(value) {
+            Ok(ParsedLine::Remove(parsed))
+        } else if let Ok(parsed) = MoveLine::try_from(value) {
+            Ok(ParsedLine::Move(parsed))
         } else if let Ok(parsed) = DirectoryLine::try_from(value) {
             Ok(ParsedLine::Directory(parsed))
         } else if let Ok(parsed) = FileLine::try_from(value) {
@@ -157,4 +266,28 @@ pub mod day7_lines_tests {
             Err(e) => panic!("{e}"),
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn parse_shell_lines() {
+        match ParsedLine::try_from("$ touch 1234 a/b c.txt") {
+            Ok(ParsedLine::Touch(parsed)) => {
+                assert_eq!(parsed.path, "a/b c.txt");
+                assert_eq!(parsed.size, 1234);
+            }
+            other => panic!("{other:?}"),
+        }
+
+        match ParsedLine::try_from("$ mv /a/b ../c") {
+            Ok(ParsedLine::Move(parsed)) => {
+                assert_eq!(parsed.from, "/a/b");
+                assert_eq!(parsed.to, "../c");
+            }
+            other => panic!("{other:?}"),
+        }
+
+        assert!(matches!(ParsedLine::try_from("$ mkdir x"), Ok(ParsedLine::MakeDirectory(_))));
+        assert!(matches!(ParsedLine::try_from("$ rm /x"), Ok(ParsedLine::Remove(_))));
+        assert!(ParsedLine::try_from("$ mv a").is_err());
+        assert!(ParsedLine::try_from("$ touch a").is_err());
+    }
+}
@@ -1,35 +1,29 @@
-mod filesystem;
-use filesystem::Filesystem;
+pub mod filesystem;
+use filesystem::{Filesystem, Node};
 
-mod lines;
+pub mod lines;
 use lines::parse;
 
-use self::filesystem::Directory;
+pub mod json;
+pub mod report;
+pub mod transcript;
 
 #[aoc(day7, part1)]
 pub fn part1(input: &str) -> u64 {
     let lines = parse(input);
     let mut fs = Filesystem::new();
-    fs.exec_lines(lines);
+    fs.exec_lines(lines).unwrap();
 
-    for dir in fs.get_directories() {
-        let sizes_sum = dir.children().iter().map(|i| fs.get_node(*i).unwrap()).map(|node| node.size()).sum::<u64>();
-        assert_eq!(dir.total_size(), sizes_sum);
+    for (path, node) in fs.walk() {
+        if let Node::Directory(dir) = node {
+            let sizes_sum = dir.children().iter().map(|i| fs.get_node(*i).unwrap()).map(|node| node.size()).sum::<u64>();
+            assert_eq!(dir.total_size(), sizes_sum, "{path}");
+        }
     }
-    
-    let sum = fs.get_directories()
-        .iter()
-        .filter_map(|dir| {
-            let size = dir.total_size();
-            if size <= 100_000 {
-                Some(size)
-            } else {
-                None
-            }
-        })
-        .sum::<u64>();
-
-    sum
+
+    fs.find(|_, node| node.is_directory() && node.size() <= 100_000)
+        .map(|(_, dir)| dir.size())
+        .sum()
 }
 
 #[aoc(day7, part2)]
@@ -39,21 +33,16 @@ pub fn part2(input: &str) -> u64 {
 
     let lines = parse(input);
     let mut fs = Filesystem::new();
-    fs.exec_lines(lines);
+    fs.exec_lines(lines).unwrap();
 
     let total_used = fs.root_dir().total_size();
     let unused = disk_space - total_used;
     let to_free = space_needed - unused;
 
-    let dirs = fs.get_directories();
-    let mut candidates = dirs
-        .iter()
-        .filter(|dir| dir.total_size() >= to_free)
-        .collect::<Vec<&&Directory>>();
-        
-    candidates.sort_by_key(|a| a.total_size());
-
-    candidates.first().unwrap().total_size()
+    fs.find(|_, node| node.is_directory() && node.size() >= to_free)
+        .map(|(_, dir)| dir.size())
+        .min()
+        .unwrap()
 }
 
 
@@ -61,10 +50,11 @@ pub fn part2(input: &str) -> u64 {
 pub mod day7_tests {
     use super::*;
 
+    pub const SAMPLE_INPUT: &str = "$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d\n$ cd a\n$ ls\ndir e\n29116 f\n2557 g\n62596 h.lst\n$ cd e\n$ ls\n584 i\n$ cd ..\n$ cd ..\n$ cd d\n$ ls\n4060174 j\n8033020 d.log\n5626152 d.ext\n7214296 k";
+
     #[test]
     fn part1_sample() {
-        let input = "$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d\n$ cd a\n$ ls\ndir e\n29116 f\n2557 g\n62596 h.lst\n$ cd e\n$ ls\n584 i\n$ cd ..\n$ cd ..\n$ cd d\n$ ls\n4060174 j\n8033020 d.log\n5626152 d.ext\n7214296 k";
-        let answer = part1(input);
+        let answer = part1(SAMPLE_INPUT);
         assert_eq!(answer, 95437);
     }
 
@@ -142,4 +132,87 @@ pub mod day7_tests {
 
         assert_eq!(answer, 1234 * 2);
     }
+
+    #[test]
+    fn part1_repeated_ls() {
+        let input = [
+            "$ cd /",
+            "$ ls",
+            "dir a",
+            "10 b",
+            "$ cd a",
+            "$ ls",
+            "20 c",
+            "$ cd ..",
+            "$ ls",
+            "dir a",
+            "10 b",
+            "$ cd a",
+            "$ ls",
+            "20 c",
+        ].join("\n");
+
+        let answer = part1(&input);
+
+        assert_eq!(answer, 20 + 30);
+    }
+
+    #[test]
+    fn conflicting_listings_are_reported() {
+        let input = [
+            "$ cd /",
+            "$ ls",
+            "dir a",
+            "10 b",
+            "$ ls",
+            "11 b",
+            "5 a",
+        ].join("\n");
+
+        let mut fs = Filesystem::new();
+        let conflicts = fs.exec_lines(parse(&input)).unwrap();
+
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].to_string(), "line 6: file 'b' listed with size 11 but was 10");
+        assert_eq!(conflicts[1].to_string(), "line 7: 'a' listed as a file but is a directory");
+        assert_eq!(fs.root_dir().total_size(), 10);
+    }
+
+    #[test]
+    fn cd_into_unknown_directory() {
+        let input = [
+            "$ cd /",
+            "$ ls",
+            "dir a",
+            "$ cd a",
+            "$ ls",
+            "dir b",
+            "$ cd /a/b",
+            "$ cd ../../c",
+        ].join("\n");
+
+        let mut fs = Filesystem::new();
+        let err = fs.exec_lines(parse(&input)).unwrap_err();
+
+        assert_eq!(err.to_string(), "line 8: no such file or directory '../../c'");
+    }
+
+    #[test]
+    fn what_if_deleted() {
+        // Without the biggest file in a, both a and / are under the limit
+        let input = format!("{SAMPLE_INPUT}\n$ cd /\n$ rm /a/h.lst\n$ rm /d\n$ rm /b.txt\n$ rm c.dat");
+        assert_eq!(part1(&input), 2 * (29116 + 2557 + 584) + 584);
+
+        // Moving e into a new directory in a counts it once more, in tmp as well as in a and e2
+        let input = format!("{SAMPLE_INPUT}\n$ mkdir /a/tmp\n$ mv /a/e /a/tmp/e2\n$ touch 100 /a/tmp/e2/extra");
+        assert_eq!(part1(&input), 95437 + 2 * 684 + 100 - 584);
+
+        let mut fs = Filesystem::new();
+        let err = fs.exec_lines(parse(&format!("{SAMPLE_INPUT}\n$ rm /x"))).unwrap_err();
+        assert_eq!(err.to_string(), "line 24: no such file or directory '/x'");
+
+        let mut fs = Filesystem::new();
+        let err = fs.exec_lines(parse(&format!("{SAMPLE_INPUT}\n$ rm /d"))).unwrap_err();
+        assert_eq!(err.to_string(), "line 24: can't remove '/d' while in it");
+    }
 }
@@ -0,0 +1,274 @@
+use std::str::FromStr;
+
+use itertools::Itertools;
+
+use super::filesystem::{Filesystem, Node};
+
+/// Number of directories between the root and `path`, so the root is 0 and `/a` is 1
+pub fn depth(path: &str) -> usize {
+    path.split('/').filter(|s| !s.is_empty()).count()
+}
+
+/// Size with a binary unit suffix like `du -h`, e.g. `92.6K`. Anything under 1K is left as it is
+pub fn human_size(size: u64) -> String {
+    const UNITS: [&str; 4] = ["K", "M", "G", "T"];
+
+    if size < 1024 {
+        return size.to_string();
+    }
+
+    let mut value = size as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1}{}", UNITS[unit])
+}
+
+/// Parses a size as a number of bytes, optionally followed by a `K`, `M`, `G` or `T` suffix
+pub fn parse_size(s: &str) -> Result<u64, String> {
+    let (digits, multiplier) = match s.chars().last() {
+        Some('K' | 'k') => (&s[..s.len() - 1], 1 << 10),
+        Some('M' | 'm') => (&s[..s.len() - 1], 1 << 20),
+        Some('G' | 'g') => (&s[..s.len() - 1], 1 << 30),
+        Some('T' | 't') => (&s[..s.len() - 1], 1 << 40),
+        _ => (s, 1),
+    };
+
+    let n = digits.parse::<u64>().map_err(|e| format!("Invalid size '{s}': {e}"))?;
+    n.checked_mul(multiplier).ok_or_else(|| format!("Size '{s}' is too large"))
+}
+
+/// Whether `name` matches `pattern`, where `*` matches any run of characters and `?` any one character
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` and where in the name it started matching, to backtrack to
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            // Let the `*` swallow one more character and try again
+            star = Some((star_p, star_n + 1));
+            p = star_p + 1;
+            n = star_n + 1;
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    File,
+    Directory,
+}
+
+impl Kind {
+    pub fn of(node: &Node) -> Self {
+        if node.is_directory() { Kind::Directory } else { Kind::File }
+    }
+}
+
+impl FromStr for Kind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "f" | "file" => Ok(Kind::File),
+            "d" | "dir" | "directory" => Ok(Kind::Directory),
+            _ => Err(format!("Unknown type '{s}', expected f or d")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortBy {
+    Path,
+    /// Largest first
+    Size,
+}
+
+impl FromStr for SortBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "path" => Ok(SortBy::Path),
+            "size" => Ok(SortBy::Size),
+            _ => Err(format!("Unknown sort '{s}', expected path or size")),
+        }
+    }
+}
+
+/// Total size of every directory at most `max_depth` below the root
+pub fn du(fs: &Filesystem, max_depth: Option<usize>, sort: SortBy) -> Vec<(String, u64)> {
+    let mut sizes = fs
+        .find(|path, node| node.is_directory() && max_depth.is_none_or(|max| depth(path) <= max))
+        .map(|(path, dir)| (path, dir.size()))
+        .collect::<Vec<_>>();
+
+    match sort {
+        SortBy::Path => sizes.sort(),
+        SortBy::Size => sizes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0))),
+    }
+
+    sizes
+}
+
+/// One `size<tab>path` line per entry, like `du`
+pub fn format_sizes(sizes: &[(String, u64)], human: bool) -> String {
+    sizes
+        .iter()
+        .map(|(path, size)| {
+            let size = if human { human_size(*size) } else { size.to_string() };
+            format!("{size}\t{path}")
+        })
+        .join("\n")
+}
+
+/// Every file and directory indented under its parent, in the order they were listed
+pub fn tree(fs: &Filesystem, human: bool) -> String {
+    fs.walk()
+        .map(|(path, node)| {
+            let indent = "  ".repeat(depth(&path));
+            let size = if human { human_size(node.size()) } else { node.size().to_string() };
+            let kind = if node.is_directory() { "dir" } else { "file" };
+            format!("{indent}- {} ({kind}, {size})", node.name())
+        })
+        .join("\n")
+}
+
+/// What `find` keeps. Anything left as `None` matches everything
+#[derive(Debug, Clone, Default)]
+pub struct FindQuery {
+    pub min_size: Option<u64>,
+    pub max_size: Option<u64>,
+    /// Glob the name, not the whole path, has to match
+    pub name: Option<String>,
+    pub kind: Option<Kind>,
+}
+
+impl FindQuery {
+    pub fn matches(&self, node: &Node) -> bool {
+        self.min_size.is_none_or(|min| node.size() >= min)
+            && self.max_size.is_none_or(|max| node.size() <= max)
+            && self.name.as_ref().is_none_or(|glob| glob_match(glob, node.name()))
+            && self.kind.is_none_or(|kind| kind == Kind::of(node))
+    }
+}
+
+/// Every file and directory that matches `query`, in the order they were listed
+pub fn find<'a>(fs: &'a Filesystem, query: &FindQuery) -> Vec<(String, &'a Node)> {
+    fs.walk().filter(|(_, node)| query.matches(node)).collect()
+}
+
+/// The `n` biggest files and directories, not counting the root which is always the biggest
+pub fn largest(fs: &Filesystem, n: usize, kind: Option<Kind>) -> Vec<(String, u64)> {
+    fs.find(|path, node| path != "/" && kind.is_none_or(|kind| kind == Kind::of(node)))
+        .map(|(path, node)| (path, node.size()))
+        .sorted_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)))
+        .take(n)
+        .collect()
+}
+
+#[cfg(test)]
+mod day7_report_tests {
+    use super::*;
+    use crate::day07::day7_tests::SAMPLE_INPUT;
+    use crate::day07::lines::parse;
+
+    fn sample() -> Filesystem {
+        let mut fs = Filesystem::new();
+        fs.exec_lines(parse(SAMPLE_INPUT)).unwrap();
+        fs
+    }
+
+    #[test]
+    fn sizes() {
+        assert_eq!(human_size(584), "584");
+        assert_eq!(human_size(94853), "92.6K");
+        assert_eq!(human_size(48381165), "46.1M");
+        assert_eq!(parse_size("5M").unwrap(), 5 * 1024 * 1024);
+        assert_eq!(parse_size("100000").unwrap(), 100000);
+        assert!(parse_size("5X").is_err());
+        assert_eq!(parse_size("20000000T"), Err("Size '20000000T' is too large".to_string()));
+    }
+
+    #[test]
+    fn globs() {
+        assert!(glob_match("*.txt", "b.txt"));
+        assert!(glob_match("d.*", "d.log"));
+        assert!(glob_match("?", "k"));
+        assert!(glob_match("*a*b*", "xxaxxbxx"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("*.txt", "c.dat"));
+        assert!(!glob_match("?", "ab"));
+        assert!(!glob_match("a*b", "abc"));
+    }
+
+    #[test]
+    fn du_sorted_and_limited() {
+        let fs = sample();
+
+        let by_size = du(&fs, Some(1), SortBy::Size);
+        assert_eq!(by_size, vec![("/".to_string(), 48381165), ("/d".to_string(), 24933642), ("/a".to_string(), 94853)]);
+
+        let by_path = du(&fs, None, SortBy::Path).into_iter().map(|(path, _)| path).collect::<Vec<_>>();
+        assert_eq!(by_path, vec!["/", "/a", "/a/e", "/d"]);
+
+        assert_eq!(format_sizes(&by_size[1..], true), "23.8M\t/d\n92.6K\t/a");
+    }
+
+    #[test]
+    fn tree_with_sizes() {
+        let fs = sample();
+        let lines = tree(&fs, true).lines().map(|l| l.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 14);
+        assert_eq!(lines[0], "- / (dir, 46.1M)");
+        assert_eq!(lines[1], "  - a (dir, 92.6K)");
+        assert_eq!(lines[2], "    - e (dir, 584)");
+        assert_eq!(lines[3], "      - i (file, 584)");
+        assert_eq!(tree(&fs, false).lines().nth(1).unwrap(), "  - a (dir, 94853)");
+    }
+
+    #[test]
+    fn find_by_query() {
+        let fs = sample();
+        let query = FindQuery {
+            min_size: Some(parse_size("5M").unwrap()),
+            name: Some("*.*".to_string()),
+            kind: Some(Kind::File),
+            ..Default::default()
+        };
+        let paths = find(&fs, &query).into_iter().map(|(path, _)| path).collect::<Vec<_>>();
+        assert_eq!(paths, vec!["/b.txt", "/c.dat", "/d/d.log", "/d/d.ext"]);
+
+        let small_dirs = FindQuery { max_size: Some(100_000), kind: Some(Kind::Directory), ..Default::default() };
+        let total = find(&fs, &small_dirs).iter().map(|(_, node)| node.size()).sum::<u64>();
+        assert_eq!(total, 95437);
+    }
+
+    #[test]
+    fn largest_entries() {
+        let fs = sample();
+
+        let paths = largest(&fs, 3, None).into_iter().map(|(path, _)| path).collect::<Vec<_>>();
+        assert_eq!(paths, vec!["/d", "/b.txt", "/c.dat"]);
+        assert_eq!(largest(&fs, 1, Some(Kind::Directory)), vec![("/d".to_string(), 24933642)]);
+        assert_eq!(largest(&fs, 100, Some(Kind::File)).len(), 10);
+    }
+}
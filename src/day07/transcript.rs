@@ -0,0 +1,104 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Writes a `$ cd`/`$ ls` transcript of the directory tree at `root`, as if someone had explored it
+/// from `/`. Entries are listed and visited in name order so the same tree always gives the same
+/// transcript. Symlinks are skipped so there are no cycles, and so are names with a newline in them,
+/// which can't be written as one line
+pub fn write_transcript(root: &Path, out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "$ cd /")?;
+    write_directory(root, out)
+}
+
+/// Like `write_transcript`, collected into a string
+pub fn transcript(root: &Path) -> io::Result<String> {
+    let mut out = vec![];
+    write_transcript(root, &mut out)?;
+
+    Ok(String::from_utf8_lossy(&out).into_owned())
+}
+
+fn write_directory(dir: &Path, out: &mut impl Write) -> io::Result<()> {
+    let mut entries = vec![];
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let file_type = entry.file_type()?;
+
+        if file_type.is_symlink() || name.contains('\n') {
+            continue;
+        }
+        entries.push((name, file_type.is_dir(), entry.metadata()?.len()));
+    }
+    entries.sort();
+
+    writeln!(out, "$ ls")?;
+    for (name, is_dir, size) in &entries {
+        if *is_dir {
+            writeln!(out, "dir {name}")?;
+        } else {
+            writeln!(out, "{size} {name}")?;
+        }
+    }
+
+    for (name, _, _) in entries.iter().filter(|(_, is_dir, _)| *is_dir) {
+        writeln!(out, "$ cd {name}")?;
+        write_directory(&dir.join(name), out)?;
+        writeln!(out, "$ cd ..")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod day7_transcript_tests {
+    use super::*;
+    use crate::day07::filesystem::Filesystem;
+    use crate::day07::lines::parse;
+    use crate::day07::part1;
+
+    #[test]
+    fn transcript_of_real_directory() {
+        let root = std::env::temp_dir().join(format!("day07_transcript_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("a/e")).unwrap();
+        fs::create_dir_all(root.join("d")).unwrap();
+        fs::write(root.join("b.txt"), vec![0; 1500]).unwrap();
+        fs::write(root.join("a/f"), vec![0; 300]).unwrap();
+        fs::write(root.join("a/e/i"), vec![0; 20]).unwrap();
+        fs::write(root.join("d/j"), "").unwrap();
+
+        let text = transcript(&root).unwrap();
+        let again = transcript(&root).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(text, again);
+        assert_eq!(text, [
+            "$ cd /",
+            "$ ls",
+            "dir a",
+            "1500 b.txt",
+            "dir d",
+            "$ cd a",
+            "$ ls",
+            "dir e",
+            "300 f",
+            "$ cd e",
+            "$ ls",
+            "20 i",
+            "$ cd ..",
+            "$ cd ..",
+            "$ cd d",
+            "$ ls",
+            "0 j",
+            "$ cd ..",
+            "",
+        ].join("\n"));
+
+        let mut fs = Filesystem::new();
+        assert!(fs.exec_lines(parse(&text)).unwrap().is_empty());
+        assert_eq!(fs.root_dir().total_size(), 1820);
+        assert_eq!(part1(&text), 1820 + 320 + 20);
+    }
+}
@@ -3,6 +3,8 @@ use std::collections::HashSet;
 use lazy_static::lazy_static;
 use regex::Regex;
 
+use crate::interval::Interval;
+
 lazy_static! {
     static ref LINE_REGEX: Regex = Regex::new(r"^Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)$").unwrap();
 }
@@ -19,15 +21,10 @@ pub struct ParsedLine {
 #[derive(Debug)]
 pub struct Input {
     lines: Vec<ParsedLine>,
-    min_x: i32,
-    max_x: i32,
 }
 
 #[aoc_generator(day15)]
 pub fn generator(input: &str) -> Input {
-    let mut min_x = i32::MAX;
-    let mut max_x = i32::MIN;
-
     let lines = input
         .lines()
         .map(|line| LINE_REGEX.captures_iter(line).collect::<Vec<_>>())
@@ -42,48 +39,51 @@ pub fn generator(input: &str) -> Input {
 
             let distance = manhattan_distance(&sensor, &beacon);
 
-            min_x = min_x.min(sensor_x - (distance as i32)).min(beacon_x - (distance as i32));
-            max_x = max_x.max(sensor_x + (distance as i32)).max(beacon_x + (distance as i32));
-
             ParsedLine { sensor, beacon, distance }
         })
         .collect::<Vec<_>>();
     
-    Input { lines, min_x, max_x }
+    Input { lines }
 }
 
 #[aoc(day15, part1)]
 pub fn part1(input: &Input) -> usize {
     // Sample input -> 10
     // Actual input -> 2_000_000
-    // let line_of_interest = 10;
-    let line_of_interest = 2_000_000;
-
-    let mut count = 0_usize;
-
-    // For each point in the line we're looking at
-    // TODO my original bounding by min_x and max_x wasn't big enough, how can we bound it correctly?
-    for x in input.min_x..=input.max_x {
-        let point: Point = (x, line_of_interest);
-
-        // Check if this point is covered by another sensor's range
-        for line in &input.lines {
-            let d = manhattan_distance(&point, &line.sensor);
-            
-            // If point is already occupied, don't consider it
-            if point == line.beacon || point == line.sensor {
-                continue;
-            }
+    row_coverage(input, 2_000_000)
+}
 
-            // If close to another sensor, beacon can't be here
-            if d <= line.distance {
-                count += 1;
-                break;
-            }
+/// Number of points in `row` where a beacon can't be
+fn row_coverage(input: &Input, row: i32) -> usize {
+    // Each sensor covers a single interval of the row, narrower the further the row is from the sensor
+    let mut intervals = input.lines
+        .iter()
+        .filter_map(|line| {
+            let half_width = line.distance.checked_sub(line.sensor.1.abs_diff(row))? as i32;
+            Some(Interval::new(line.sensor.0 - half_width, line.sensor.0 + half_width))
+        })
+        .collect::<Vec<_>>();
+    intervals.sort();
+
+    let mut merged: Vec<Interval<i32>> = vec![];
+    for interval in intervals {
+        match merged.last().and_then(|last| last.union_if_adjacent(&interval)) {
+            Some(union) => *merged.last_mut().unwrap() = union,
+            None => merged.push(interval),
         }
     }
 
-    count
+    let covered = merged.iter().map(|i| i.len() as usize).sum::<usize>();
+
+    // Known beacons in the row are covered, but obviously a beacon can be there
+    let beacons = input.lines
+        .iter()
+        .map(|line| line.beacon)
+        .filter(|beacon| beacon.1 == row && merged.iter().any(|i| i.contains_point(beacon.0)))
+        .collect::<HashSet<_>>()
+        .len();
+
+    covered - beacons
 }
 
 #[aoc(day15, part2)]
@@ -169,12 +169,12 @@ mod day15_tests {
         "Sensor at x=20, y=1: closest beacon is at x=15, y=3",
     );
 
-    // #[test]
-    // fn part1_sample_input() {
-    //     let input = generator(SAMPLE_INPUT);
-    //     let answer = part1(&input);
-    //     assert_eq!(answer, 26);
-    // }
+    #[test]
+    fn part1_sample_input() {
+        let input = generator(SAMPLE_INPUT);
+        let answer = row_coverage(&input, 10);
+        assert_eq!(answer, 26);
+    }
 
     #[test]
     fn part2_sample_input() {
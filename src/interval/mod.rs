@@ -0,0 +1,170 @@
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+mod set;
+pub use set::IntervalSet;
+
+mod tree;
+pub use tree::IntervalTree;
+
+/// Integer types an `Interval` can be made of. Intervals can go right up to `MIN` and `MAX`, but
+/// lengths have to fit in the type too, so `len` and `coverage` panic for anything longer than `MAX`
+pub trait Bound: Copy + Ord + Debug + Display + FromStr + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+    const MAX: Self;
+}
+
+macro_rules! impl_bound {
+    ($($t:ty),*) => {
+        $(impl Bound for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const MAX: Self = <$t>::MAX;
+        })*
+    };
+}
+
+impl_bound!(i32, i64, isize, u32, u64, usize);
+
+/// Inclusive range of integers `start..=end`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+impl<T: Bound> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        assert!(start <= end, "Interval start {start} is after end {end}");
+        Self { start, end }
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    /// Number of integers in the interval. Panics if that's more than `T::MAX`, e.g. for `i32::MIN..=i32::MAX`
+    pub fn len(&self) -> T {
+        self.end - self.start + T::ONE
+    }
+
+    pub fn contains(&self, other: &Interval<T>) -> bool {
+        self.start <= other.start && self.end >= other.end
+    }
+
+    pub fn contains_point(&self, point: T) -> bool {
+        self.start <= point && point <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if self.overlaps(other) {
+            Some(Interval::new(self.start.max(other.start), self.end.min(other.end)))
+        } else {
+            None
+        }
+    }
+
+    /// Single interval covering both, if they overlap or touch end to end
+    pub fn union_if_adjacent(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        let (first, second) = if self.start <= other.start { (self, other) } else { (other, self) };
+
+        // Checked this way round so nothing overflows at the ends of the type
+        if second.start <= first.end || (first.end < T::MAX && first.end + T::ONE == second.start) {
+            Some(Interval::new(first.start, first.end.max(second.end)))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Bound> Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+impl<T: Bound> FromStr for Interval<T> {
+    type Err = String;
+
+    /// Parses `a-b`. Skips the first character when looking for the separator so `-3-5` works
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let sep = s
+            .char_indices()
+            .skip(1)
+            .find(|(_, ch)| *ch == '-')
+            .map(|(i, _)| i)
+            .ok_or(format!("Interval '{s}' missing '-'"))?;
+
+        let (start, end) = (&s[..sep], &s[sep + 1..]);
+        let start = start.trim().parse::<T>().or(Err(format!("Unable to parse interval start '{start}'")))?;
+        let end = end.trim().parse::<T>().or(Err(format!("Unable to parse interval end '{end}'")))?;
+
+        if start > end {
+            return Err(format!("Interval '{s}' start is after end"));
+        }
+
+        Ok(Interval { start, end })
+    }
+}
+
+#[cfg(test)]
+mod interval_tests {
+    use super::*;
+
+    fn i(start: i32, end: i32) -> Interval<i32> {
+        Interval::new(start, end)
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("2-8".parse::<Interval<i32>>(), Ok(i(2, 8)));
+        assert_eq!("-3--1".parse::<Interval<i32>>(), Ok(i(-3, -1)));
+        assert!("8-2".parse::<Interval<i32>>().is_err());
+        assert!("8".parse::<Interval<i32>>().is_err());
+        assert!("a-b".parse::<Interval<u32>>().is_err());
+    }
+
+    #[test]
+    fn contains_and_overlaps() {
+        assert!(i(2, 8).contains(&i(3, 7)));
+        assert!(i(2, 8).contains(&i(2, 8)));
+        assert!(!i(3, 7).contains(&i(2, 8)));
+        assert!(i(5, 7).overlaps(&i(7, 9)));
+        assert!(!i(2, 4).overlaps(&i(6, 8)));
+        assert!(i(2, 4).contains_point(4));
+    }
+
+    #[test]
+    fn intersection_and_union() {
+        assert_eq!(i(5, 7).intersection(&i(7, 9)), Some(i(7, 7)));
+        assert_eq!(i(2, 4).intersection(&i(6, 8)), None);
+        assert_eq!(i(2, 4).union_if_adjacent(&i(5, 8)), Some(i(2, 8)));
+        assert_eq!(i(5, 8).union_if_adjacent(&i(2, 6)), Some(i(2, 8)));
+        assert_eq!(i(2, 4).union_if_adjacent(&i(6, 8)), None);
+        assert_eq!(i(-5, 5).len(), 11);
+    }
+
+    #[test]
+    fn near_type_bounds() {
+        let low = i(i32::MIN, -1);
+        let high = i(0, i32::MAX);
+        assert_eq!(low.union_if_adjacent(&high), Some(i(i32::MIN, i32::MAX)));
+        assert_eq!(i(i32::MIN, i32::MIN).union_if_adjacent(&i(i32::MAX, i32::MAX)), None);
+        assert_eq!(i(1, i32::MAX).len(), i32::MAX);
+
+        let top = Interval::new(u32::MAX - 1, u32::MAX);
+        assert_eq!(top.union_if_adjacent(&Interval::new(u32::MAX, u32::MAX)), Some(top));
+        assert_eq!(Interval::new(0, 0).union_if_adjacent(&top), None);
+        assert_eq!(top.len(), 2);
+    }
+}
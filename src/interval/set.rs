@@ -0,0 +1,181 @@
+use std::fmt::Display;
+
+use itertools::Itertools;
+
+use super::{Bound, Interval};
+
+/// Set of integers stored as sorted, disjoint, non-adjacent intervals
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Bound> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { intervals: vec![] }
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        // Everything that overlaps or touches the new interval is in one contiguous run
+        let first = self.intervals.partition_point(|i| i.end() < interval.start() && interval.union_if_adjacent(i).is_none());
+        let last = self.intervals.partition_point(|i| i.start() <= interval.end() || interval.union_if_adjacent(i).is_some());
+
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |acc, i| acc.union_if_adjacent(i).unwrap());
+
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn contains_point(&self, point: T) -> bool {
+        let i = self.intervals.partition_point(|i| i.end() < point);
+        self.intervals.get(i).is_some_and(|i| i.contains_point(point))
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut set = self.clone();
+        for interval in &other.intervals {
+            set.insert(*interval);
+        }
+        set
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = vec![];
+        let (mut a, mut b) = (0, 0);
+
+        while a < self.intervals.len() && b < other.intervals.len() {
+            let (x, y) = (&self.intervals[a], &other.intervals[b]);
+            if let Some(i) = x.intersection(y) {
+                intervals.push(i);
+            }
+
+            // Whichever ends first can't overlap anything else in the other set
+            if x.end() < y.end() {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = vec![];
+
+        for interval in &self.intervals {
+            let mut start = interval.start();
+            let mut remaining = true;
+
+            // Only steps past a cut's ends that are inside `interval`, so nothing overflows at the ends of the type
+            for cut in other.intervals.iter().filter(|c| c.overlaps(interval)) {
+                if cut.start() > start {
+                    intervals.push(Interval::new(start, cut.start() - T::ONE));
+                }
+                if cut.end() >= interval.end() {
+                    remaining = false;
+                    break;
+                }
+                start = cut.end() + T::ONE;
+            }
+
+            if remaining {
+                intervals.push(Interval::new(start, interval.end()));
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    /// Total number of integers in the set
+    pub fn coverage(&self) -> T {
+        self.intervals.iter().fold(T::ZERO, |acc, i| acc + i.len())
+    }
+
+    /// Integers between the lowest and highest members that aren't in the set
+    pub fn gaps(&self) -> IntervalSet<T> {
+        // Intervals in the set never touch, so there's always room either side of a gap
+        let intervals = self.intervals
+            .iter()
+            .tuple_windows()
+            .map(|(a, b)| Interval::new(a.end() + T::ONE, b.start() - T::ONE))
+            .collect();
+
+        IntervalSet { intervals }
+    }
+}
+
+impl<T: Bound> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+impl<T: Bound> Display for IntervalSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{{}}}", self.intervals.iter().join(", "))
+    }
+}
+
+#[cfg(test)]
+mod interval_set_tests {
+    use super::*;
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals.iter().map(|(start, end)| Interval::new(*start, *end)).collect()
+    }
+
+    #[test]
+    fn insert_merges() {
+        let s = set(&[(10, 12), (1, 3), (5, 6), (4, 4), (20, 25), (11, 21)]);
+        assert_eq!(s.intervals(), set(&[(1, 6), (10, 25)]).intervals());
+        assert_eq!(s.intervals().len(), 2);
+        assert_eq!(s.coverage(), 22);
+        assert!(s.contains_point(4));
+        assert!(!s.contains_point(8));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[(1, 10), (20, 30)]);
+        let b = set(&[(5, 25)]);
+
+        assert_eq!(a.union(&b), set(&[(1, 30)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25)]));
+        assert_eq!(a.difference(&b), set(&[(1, 4), (26, 30)]));
+        assert_eq!(b.difference(&a), set(&[(11, 19)]));
+        assert_eq!(set(&[(1, 10)]).difference(&set(&[(3, 4), (6, 7)])), set(&[(1, 2), (5, 5), (8, 10)]));
+        assert!(set(&[(3, 4)]).difference(&set(&[(1, 10)])).is_empty());
+    }
+
+    #[test]
+    fn gaps() {
+        assert_eq!(set(&[(1, 2), (5, 5), (8, 10)]).gaps(), set(&[(3, 4), (6, 7)]));
+        assert!(set(&[(1, 10)]).gaps().is_empty());
+    }
+
+    #[test]
+    fn near_type_bounds() {
+        let full = set(&[(i32::MIN, i32::MAX)]);
+        let middle = set(&[(-5, 5)]);
+        assert_eq!(full.difference(&middle).intervals(), set(&[(i32::MIN, -6), (6, i32::MAX)]).intervals());
+        assert!(full.difference(&full).is_empty());
+
+        let ends = set(&[(i32::MIN, i32::MIN), (i32::MAX, i32::MAX)]);
+        assert_eq!(ends.intervals().len(), 2);
+        assert_eq!(ends.gaps().intervals(), set(&[(i32::MIN + 1, i32::MAX - 1)]).intervals());
+    }
+}
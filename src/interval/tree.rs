@@ -0,0 +1,146 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::{Bound, Interval};
+
+/// Static interval tree for answering overlap queries over many intervals without comparing every pair.
+/// Results refer to intervals by their index in the slice the tree was built from
+#[derive(Debug, Clone)]
+pub struct IntervalTree<T> {
+    /// Intervals with their original index, sorted by start. The tree is implicit, the root of
+    /// `lo..hi` is at `(lo + hi) / 2`
+    by_start: Vec<(Interval<T>, usize)>,
+    /// Highest end in the subtree rooted at each position of `by_start`
+    max_end: Vec<T>,
+    starts: Vec<T>,
+    ends: Vec<T>,
+}
+
+impl<T: Bound> IntervalTree<T> {
+    pub fn new(intervals: &[Interval<T>]) -> Self {
+        let mut by_start = intervals.iter().copied().zip(0..).collect::<Vec<_>>();
+        by_start.sort();
+
+        let mut max_end = by_start.iter().map(|(i, _)| i.end()).collect::<Vec<_>>();
+        Self::build(&by_start, &mut max_end, 0, by_start.len());
+
+        let mut starts = intervals.iter().map(|i| i.start()).collect::<Vec<_>>();
+        let mut ends = intervals.iter().map(|i| i.end()).collect::<Vec<_>>();
+        starts.sort();
+        ends.sort();
+
+        Self { by_start, max_end, starts, ends }
+    }
+
+    fn build(by_start: &[(Interval<T>, usize)], max_end: &mut [T], lo: usize, hi: usize) -> Option<T> {
+        if lo >= hi {
+            return None;
+        }
+
+        let mid = (lo + hi) / 2;
+        let left = Self::build(by_start, max_end, lo, mid);
+        let right = Self::build(by_start, max_end, mid + 1, hi);
+
+        let max = [left, right].into_iter().flatten().fold(by_start[mid].0.end(), T::max);
+        max_end[mid] = max;
+        Some(max)
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_start.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_start.is_empty()
+    }
+
+    /// Number of intervals overlapping `query`
+    pub fn count_overlapping(&self, query: &Interval<T>) -> usize {
+        // An interval can't both start after the query and end before it, so nothing is removed twice
+        let starts_after = self.starts.len() - self.starts.partition_point(|s| *s <= query.end());
+        let ends_before = self.ends.partition_point(|e| *e < query.start());
+
+        self.len() - starts_after - ends_before
+    }
+
+    /// Indexes of the intervals overlapping `query`, in ascending order
+    pub fn overlapping(&self, query: &Interval<T>) -> Vec<usize> {
+        let mut found = vec![];
+        self.visit(query, 0, self.len(), &mut found);
+        found.sort();
+        found
+    }
+
+    fn visit(&self, query: &Interval<T>, lo: usize, hi: usize, found: &mut Vec<usize>) {
+        if lo >= hi {
+            return;
+        }
+
+        let mid = (lo + hi) / 2;
+        // Nothing in this subtree reaches the query
+        if self.max_end[mid] < query.start() {
+            return;
+        }
+
+        self.visit(query, lo, mid, found);
+
+        // Everything from here on starts after the query ends
+        let (interval, index) = &self.by_start[mid];
+        if interval.start() > query.end() {
+            return;
+        }
+        if interval.overlaps(query) {
+            found.push(*index);
+        }
+
+        self.visit(query, mid + 1, hi, found);
+    }
+
+    /// Every pair of overlapping intervals as `(i, j)` with `i < j`, sorted
+    pub fn overlapping_pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = vec![];
+        // Intervals that haven't ended yet, soonest end first
+        let mut active: BinaryHeap<Reverse<(T, usize)>> = BinaryHeap::new();
+
+        for (interval, index) in &self.by_start {
+            while let Some(Reverse((end, _))) = active.peek() {
+                if *end >= interval.start() {
+                    break;
+                }
+                active.pop();
+            }
+
+            pairs.extend(active.iter().map(|Reverse((_, other))| (*other.min(index), *other.max(index))));
+            active.push(Reverse((interval.end(), *index)));
+        }
+
+        pairs.sort();
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod interval_tree_tests {
+    use super::*;
+
+    #[test]
+    fn queries() {
+        let intervals = [(1, 3), (2, 6), (8, 9), (5, 5), (10, 20)]
+            .map(|(start, end)| Interval::new(start, end));
+        let tree = IntervalTree::new(&intervals);
+
+        let query = Interval::new(4, 8);
+        assert_eq!(tree.count_overlapping(&query), 3);
+        assert_eq!(tree.overlapping(&query), vec![1, 2, 3]);
+        assert_eq!(tree.overlapping(&Interval::new(21, 30)), Vec::<usize>::new());
+        assert_eq!(tree.overlapping_pairs(), vec![(0, 1), (1, 3)]);
+    }
+
+    #[test]
+    fn empty() {
+        let tree = IntervalTree::<i32>::new(&[]);
+        assert_eq!(tree.count_overlapping(&Interval::new(1, 2)), 0);
+        assert!(tree.overlapping(&Interval::new(1, 2)).is_empty());
+        assert!(tree.overlapping_pairs().is_empty());
+    }
+}
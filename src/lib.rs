@@ -19,4 +19,6 @@ pub mod day13;
 pub mod day14;
 pub mod day15;
 
+pub mod interval;
+
 aoc_lib!{ year = 2022 }